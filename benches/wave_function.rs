@@ -0,0 +1,76 @@
+//! Times `WaveFunction::collapse_for` on a few boards: `cargo bench --bench wave_function`.
+//!
+//! To compare candidate representations, run this file against a checkout of 3d108da, the last
+//! commit with `Vec<usize>`-backed tiles, and of ad17066, which moved `CellTile` to a `u64`
+//! bitset. Best and mean of 20 runs on one machine:
+//!
+//! ```text
+//!              Vec<usize> tiles     u64 tiles
+//! easy 9x9     153 us / 176 us      92 us / 117 us
+//! hard 9x9     26.7 ms / 35.3 ms    15.7 ms / 17.7 ms
+//! empty 9x9    1.66 ms / 1.90 ms    0.62 ms / 0.66 ms
+//! empty 16x16  14.8 ms / 24.7 ms    4.46 ms / 5.27 ms
+//! ```
+
+use std::time::{Duration, Instant};
+
+use sudoku_solver::core::{
+    wave_function_collapse::{CollapseResult, WaveFunction},
+    GameBoard,
+};
+
+const RUNS: u32 = 20;
+
+fn time_collapse<const BOX_SIZE: usize>(name: &str, gameboard: GameBoard<BOX_SIZE>) {
+    let mut best = Duration::MAX;
+    let mut total = Duration::ZERO;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let result = WaveFunction::collapse_for(gameboard.clone());
+        let elapsed = start.elapsed();
+        assert!(matches!(result, CollapseResult::Solved(_)));
+        best = best.min(elapsed);
+        total += elapsed;
+    }
+
+    println!(
+        "{:<12} best {:>10?}  mean {:>10?}",
+        name,
+        best,
+        total / RUNS
+    );
+}
+
+fn main() {
+    time_collapse(
+        "easy 9x9",
+        GameBoard::<3>::new(vec![
+            vec![3, 0, 6, 5, 0, 8, 4, 0, 0],
+            vec![5, 2, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 8, 7, 0, 0, 0, 0, 3, 1],
+            vec![0, 0, 3, 0, 1, 0, 0, 8, 0],
+            vec![9, 0, 0, 8, 6, 3, 0, 0, 5],
+            vec![0, 5, 0, 0, 9, 0, 6, 0, 0],
+            vec![1, 3, 0, 0, 0, 0, 2, 5, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 7, 4],
+            vec![0, 0, 5, 2, 0, 6, 3, 0, 0],
+        ]),
+    );
+    time_collapse(
+        "hard 9x9",
+        GameBoard::<3>::new(vec![
+            vec![8, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 3, 6, 0, 0, 0, 0, 0],
+            vec![0, 7, 0, 0, 9, 0, 2, 0, 0],
+            vec![0, 5, 0, 0, 0, 7, 0, 0, 0],
+            vec![0, 0, 0, 0, 4, 5, 7, 0, 0],
+            vec![0, 0, 0, 1, 0, 0, 0, 3, 0],
+            vec![0, 0, 1, 0, 0, 0, 0, 6, 8],
+            vec![0, 0, 8, 5, 0, 0, 0, 1, 0],
+            vec![0, 9, 0, 0, 0, 0, 4, 0, 0],
+        ]),
+    );
+    time_collapse("empty 9x9", GameBoard::<3>::create_empty());
+    time_collapse("empty 16x16", GameBoard::<4>::create_empty());
+}
@@ -198,7 +198,7 @@ fn draw_board(
 
             text.sections[0].value = match wave_function.state.entropy_queue.get_priority(&pos) {
                 Some(cell_tile)
-                    if cell_tile.contains(&(tile_pos.x() * BOARD_ORDER + tile_pos.y() + 1)) =>
+                    if cell_tile.contains(tile_pos.x() * BOARD_ORDER + tile_pos.y() + 1) =>
                 {
                     format!("{}", tile_pos.x() * BOARD_ORDER + tile_pos.y() + 1)
                 }
@@ -226,6 +226,7 @@ fn keyboard_simulate_wave_function(
                 wave_function.backtrack_prev_frame();
             }
         } else if keys.pressed(KeyCode::Back) {
+            // Before the first guess there is nothing to undo and this leaves the board alone.
             wave_function.force_backtrack_prev_frame();
         }
     }
@@ -1,29 +1,52 @@
 use std::{
     io::{stdout, Write},
-    thread::sleep,
     time::Duration,
 };
 
-use sudoku_solver::{gameboard::GameBoard, utils::Vec2D};
+use sudoku_solver::{
+    core::{
+        solver::{solver_by_name, solver_names, SolveOutcome, SolverLimits},
+        Cell, GameBoard,
+    },
+    utils::Vec2D,
+};
 
-fn main() -> Result<(), std::io::Error> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut stdout = stdout();
 
-    let mut gameboard = GameBoard::default();
+    let name = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "backtracking".to_string());
+    let mut solver = solver_by_name::<3>(&name).ok_or_else(|| {
+        format!(
+            "unknown solver `{}`, expected one of: {}",
+            name,
+            solver_names::<3>().join(", ")
+        )
+    })?;
+    if let Some(seconds) = std::env::args().nth(2) {
+        solver.set_limits(SolverLimits::timeout(Duration::from_secs_f64(
+            seconds.parse()?,
+        )));
+    }
 
-    gameboard.set_cell(Vec2D::new(1, 2), 5);
+    let mut gameboard = GameBoard::<3>::create_empty();
 
-    stdout.write(b"\x1B[2J\x1B[1;1H")?;
-    stdout.write(format!("{:?}", gameboard).as_bytes())?;
-    stdout.flush()?;
-    sleep(Duration::new(1, 0));
+    gameboard[Vec2D::new(1, 2)] = Cell::Given(5);
 
-    gameboard.backtracking_solver();
+    stdout.write_all(format!("{:?}", gameboard).as_bytes())?;
 
-    loop {
-        stdout.write(b"\x1B[2J\x1B[1;1H")?;
-        stdout.write(format!("{:?}", gameboard).as_bytes())?;
-        stdout.flush()?;
-        sleep(Duration::new(1, 0));
+    match solver.solve(&gameboard) {
+        SolveOutcome::Solved(solution) => {
+            stdout.write_all(format!("\n{:?}", solution).as_bytes())?
+        }
+        SolveOutcome::Unsolvable => stdout.write_all(b"\nno solution\n")?,
+        SolveOutcome::GaveUp {
+            reason, deepest, ..
+        } => stdout.write_all(format!("\ngave up: {:?}\n{:?}", reason, deepest).as_bytes())?,
     }
+    stdout.write_all(format!("{}\n", solver.stats()).as_bytes())?;
+    stdout.flush()?;
+
+    Ok(())
 }
@@ -1,6 +1,6 @@
-use std::io;
+use std::{io, time::Duration};
 use sudoku_solver::{
-    core::{wave_function_collapse::WaveFunction, Cell, GameBoard},
+    core::{solver::SolverLimits, wave_function_collapse::WaveFunction, Cell, GameBoard},
     utils::Vec2D,
 };
 
@@ -21,9 +21,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     wave_fn.print(&mut stdout)?;
     // sleep(Duration::from_secs(3));
 
-    loop {
-        if !wave_fn.simulate_generation() {
-            wave_fn.backtrack_prev_frame();
+    let limits = SolverLimits {
+        max_nodes: Some(100_000),
+        ..SolverLimits::timeout(Duration::from_secs(600))
+    };
+
+    for generation in 0.. {
+        if wave_fn.state.entropy_queue.is_empty() && !wave_fn.state.is_contradiction() {
+            println!("solved after {} generations", generation);
+            break;
+        }
+        if let Some(reason) = limits.check(generation) {
+            println!("gave up after {} generations: {:?}", generation, reason);
+            break;
+        }
+
+        if !wave_fn.simulate_generation() && !wave_fn.force_backtrack_prev_frame() {
+            println!("no solution");
+            break;
         }
 
         wave_fn.print(&mut stdout)?;
@@ -33,4 +48,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .arg("pause")
             .status();
     }
+    println!("{}", wave_fn.stats());
+
+    Ok(())
 }
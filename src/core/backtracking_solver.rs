@@ -0,0 +1,254 @@
+use super::{
+    solver::{SearchMonitor, SolverStats},
+    Cell, GameBoard,
+};
+use crate::utils::Vec2D;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyCellHeuristic {
+    FirstEmpty,
+    LastEmpty,
+    #[default]
+    MinimumRemainingValues,
+}
+
+impl<const BOX_SIZE: usize> GameBoard<BOX_SIZE> {
+    pub fn backtracking_solver(&mut self) -> bool {
+        self.backtracking_solver_with(EmptyCellHeuristic::default())
+    }
+
+    pub fn backtracking_solver_with(&mut self, heuristic: EmptyCellHeuristic) -> bool {
+        self.backtracking_solver_with_stats(heuristic).0
+    }
+
+    pub fn backtracking_solver_with_stats(
+        &mut self,
+        heuristic: EmptyCellHeuristic,
+    ) -> (bool, SolverStats) {
+        let mut monitor = SearchMonitor::default();
+        let solved = self.backtracking_solver_monitored(heuristic, &mut monitor);
+        (solved, monitor.finish())
+    }
+
+    /// On interruption the board keeps the guesses of the branch being explored.
+    pub(super) fn backtracking_solver_monitored(
+        &mut self,
+        heuristic: EmptyCellHeuristic,
+        monitor: &mut SearchMonitor<BOX_SIZE>,
+    ) -> bool {
+        self.is_consistent() && self.backtrack(heuristic, monitor, 0)
+    }
+
+    fn backtrack(
+        &mut self,
+        heuristic: EmptyCellHeuristic,
+        monitor: &mut SearchMonitor<BOX_SIZE>,
+        depth: usize,
+    ) -> bool {
+        if !monitor.visit() {
+            return false;
+        }
+        monitor.descended(depth);
+        monitor.reached(depth, || self.clone());
+
+        match self.choose_empty_cell(heuristic) {
+            None => return true,
+            Some(pos) => {
+                for num in 1..=Self::BOARD_SIZE {
+                    if self.is_valid_place(pos, num) {
+                        self[pos] = Cell::Guess(num);
+                        if self.backtrack(heuristic, monitor, depth + 1) {
+                            return true;
+                        }
+                        if monitor.is_interrupted() {
+                            return false;
+                        }
+                        self[pos] = Cell::Empty;
+                        monitor.backtracked();
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        self.solutions().take(limit).count()
+    }
+
+    pub fn solutions(&self) -> Solutions<BOX_SIZE> {
+        self.solutions_with(EmptyCellHeuristic::default())
+    }
+
+    pub fn solutions_with(&self, heuristic: EmptyCellHeuristic) -> Solutions<BOX_SIZE> {
+        self.solutions_monitored(heuristic, SearchMonitor::default())
+    }
+
+    pub(super) fn solutions_monitored(
+        &self,
+        heuristic: EmptyCellHeuristic,
+        monitor: SearchMonitor<BOX_SIZE>,
+    ) -> Solutions<BOX_SIZE> {
+        Solutions {
+            gameboard: self.clone(),
+            heuristic,
+            frames: Vec::new(),
+            started: false,
+            monitor,
+        }
+    }
+
+    fn empty_cells(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        (0..Self::BOARD_SIZE)
+            .flat_map(|i| (0..Self::BOARD_SIZE).map(move |j| Vec2D::new(i, j)))
+            .filter(|&pos| self[pos] == Cell::Empty)
+    }
+
+    fn choose_empty_cell(&self, heuristic: EmptyCellHeuristic) -> Option<Vec2D> {
+        match heuristic {
+            EmptyCellHeuristic::FirstEmpty => self.empty_cells().next(),
+            EmptyCellHeuristic::LastEmpty => self.empty_cells().last(),
+            EmptyCellHeuristic::MinimumRemainingValues => {
+                let mut chosen = None;
+                for pos in self.empty_cells() {
+                    let remaining_values = (1..=Self::BOARD_SIZE)
+                        .filter(|&num| self.is_valid_place(pos, num))
+                        .count();
+
+                    match chosen {
+                        Some((_, fewest_values)) if fewest_values <= remaining_values => {}
+                        _ => chosen = Some((pos, remaining_values)),
+                    }
+                    if remaining_values <= 1 {
+                        break;
+                    }
+                }
+                chosen.map(|(pos, _)| pos)
+            }
+        }
+    }
+
+    fn is_present_in_rows_or_cols(&self, idx: Vec2D, num: usize) -> bool {
+        let (row_idx, col_idx) = (idx.x(), idx.y());
+
+        for idx in 0..Self::BOARD_SIZE {
+            match self[Vec2D::new(row_idx, idx)] {
+                Cell::Given(x) | Cell::Guess(x) if x == num => return true,
+                _ => {}
+            }
+            match self[Vec2D::new(idx, col_idx)] {
+                Cell::Given(x) | Cell::Guess(x) if x == num => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn is_present_in_box(&self, pos: Vec2D, num: usize) -> bool {
+        self.box_cell_positions(pos)
+            .any(|pos| matches!(self[pos], Cell::Given(x) | Cell::Guess(x) if x == num))
+    }
+
+    pub fn is_valid_place(&self, pos: Vec2D, num: usize) -> bool {
+        !self.is_present_in_rows_or_cols(pos, num) && !self.is_present_in_box(pos, num)
+    }
+}
+
+pub struct Solutions<const BOX_SIZE: usize> {
+    gameboard: GameBoard<BOX_SIZE>,
+    heuristic: EmptyCellHeuristic,
+    // Each frame holds an empty cell of the search path and the last number tried in it.
+    frames: Vec<(Vec2D, usize)>,
+    started: bool,
+    pub(super) monitor: SearchMonitor<BOX_SIZE>,
+}
+
+impl<const BOX_SIZE: usize> Iterator for Solutions<BOX_SIZE> {
+    type Item = GameBoard<BOX_SIZE>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if !self.gameboard.is_consistent() {
+                return None;
+            }
+            match self.gameboard.choose_empty_cell(self.heuristic) {
+                None => return Some(self.gameboard.clone()),
+                Some(pos) => self.frames.push((pos, 0)),
+            }
+        }
+
+        while let Some(&(pos, last_num)) = self.frames.last() {
+            if !self.monitor.visit() {
+                return None;
+            }
+            if last_num != 0 {
+                self.monitor.backtracked();
+            }
+            self.gameboard[pos] = Cell::Empty;
+
+            match (last_num + 1..=GameBoard::<BOX_SIZE>::BOARD_SIZE)
+                .find(|&num| self.gameboard.is_valid_place(pos, num))
+            {
+                Some(num) => {
+                    self.gameboard[pos] = Cell::Guess(num);
+                    self.frames.last_mut().unwrap().1 = num;
+                    self.monitor.descended(self.frames.len());
+                    self.monitor
+                        .reached(self.frames.len(), || self.gameboard.clone());
+
+                    match self.gameboard.choose_empty_cell(self.heuristic) {
+                        None => return Some(self.gameboard.clone()),
+                        Some(next_pos) => self.frames.push((next_pos, 0)),
+                    }
+                }
+                None => {
+                    self.frames.pop();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EmptyCellHeuristic;
+    use crate::core::{test_fixtures, GameBoard};
+
+    #[test]
+    fn solves_every_box_size() {
+        let mut gameboard = GameBoard::<2>::new(vec![
+            vec![1, 0, 0, 0],
+            vec![0, 0, 3, 0],
+            vec![0, 4, 0, 0],
+            vec![0, 0, 0, 2],
+        ]);
+        assert!(gameboard.backtracking_solver());
+        assert!(gameboard.is_solved());
+
+        let mut gameboard = GameBoard::<4>::create_empty();
+        assert!(gameboard.backtracking_solver());
+        assert!(gameboard.is_solved());
+    }
+
+    #[test]
+    fn heuristics_agree_on_unique_solution() {
+        let gameboard = test_fixtures::classic();
+
+        let solutions = [
+            EmptyCellHeuristic::FirstEmpty,
+            EmptyCellHeuristic::LastEmpty,
+            EmptyCellHeuristic::MinimumRemainingValues,
+        ]
+        .map(|heuristic| {
+            let mut solved = gameboard.clone();
+            assert!(solved.backtracking_solver_with(heuristic));
+            solved.as_raw()
+        });
+
+        assert_eq!(solutions[0], solutions[1]);
+        assert_eq!(solutions[1], solutions[2]);
+    }
+}
@@ -0,0 +1,293 @@
+use super::{solver::SearchMonitor, wave_function_collapse::Propagation, Cell, GameBoard};
+use crate::utils::Vec2D;
+
+const ROOT: usize = 0;
+
+pub struct DancingLinks<const BOX_SIZE: usize> {
+    gameboard: GameBoard<BOX_SIZE>,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    column_size: Vec<usize>,
+    // Candidate placement that each matrix row stands for, indexed by node.
+    node_candidate: Vec<(Vec2D, usize)>,
+    partial_solution: Vec<usize>,
+    nodes: u64,
+    updates: u64,
+    // Candidate rows removed from the matrix while covering columns.
+    eliminations: u64,
+}
+
+impl<const BOX_SIZE: usize> DancingLinks<BOX_SIZE> {
+    const BOARD_SIZE: usize = BOX_SIZE * BOX_SIZE;
+    const NUM_COLUMNS: usize = 4 * Self::BOARD_SIZE * Self::BOARD_SIZE;
+
+    pub fn build(gameboard: &GameBoard<BOX_SIZE>) -> Self {
+        let num_headers = Self::NUM_COLUMNS + 1;
+        let mut dlx = Self {
+            gameboard: gameboard.clone(),
+            left: (0..num_headers)
+                .map(|idx| (idx + num_headers - 1) % num_headers)
+                .collect(),
+            right: (0..num_headers)
+                .map(|idx| (idx + 1) % num_headers)
+                .collect(),
+            up: (0..num_headers).collect(),
+            down: (0..num_headers).collect(),
+            column: (0..num_headers).collect(),
+            column_size: vec![0; num_headers],
+            node_candidate: vec![(Vec2D::new(0, 0), 0); num_headers],
+            partial_solution: Vec::new(),
+            nodes: 0,
+            updates: 0,
+            eliminations: 0,
+        };
+
+        for i in 0..Self::BOARD_SIZE {
+            for j in 0..Self::BOARD_SIZE {
+                let pos = Vec2D::new(i, j);
+                match gameboard[pos] {
+                    // Guesses stay fixed like givens, so a wrong one leaves no solution.
+                    Cell::Given(val) | Cell::Guess(val) => dlx.add_candidate(pos, val),
+                    _ => (1..=Self::BOARD_SIZE).for_each(|val| dlx.add_candidate(pos, val)),
+                }
+            }
+        }
+
+        dlx
+    }
+
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    pub fn updates(&self) -> u64 {
+        self.updates
+    }
+
+    pub fn first_solution(&mut self) -> Option<GameBoard<BOX_SIZE>> {
+        self.solve(1).pop()
+    }
+
+    pub fn solve(&mut self, limit: usize) -> Vec<GameBoard<BOX_SIZE>> {
+        self.solve_monitored(limit, &mut SearchMonitor::default())
+    }
+
+    pub(super) fn solve_monitored(
+        &mut self,
+        limit: usize,
+        monitor: &mut SearchMonitor<BOX_SIZE>,
+    ) -> Vec<GameBoard<BOX_SIZE>> {
+        let mut solutions = Vec::new();
+        let eliminations = self.eliminations;
+        if limit > 0 {
+            self.search(limit, &mut solutions, monitor);
+        }
+        monitor.propagated(Propagation {
+            forced_placements: 0,
+            eliminations: (self.eliminations - eliminations) as usize,
+        });
+        solutions
+    }
+
+    fn constraint_columns(pos: Vec2D, val: usize) -> [usize; 4] {
+        let board_area = Self::BOARD_SIZE * Self::BOARD_SIZE;
+        let box_idx = (pos.x() / BOX_SIZE) * BOX_SIZE + pos.y() / BOX_SIZE;
+        let digit = val - 1;
+
+        [
+            pos.project(Self::BOARD_SIZE),
+            board_area + pos.x() * Self::BOARD_SIZE + digit,
+            2 * board_area + pos.y() * Self::BOARD_SIZE + digit,
+            3 * board_area + box_idx * Self::BOARD_SIZE + digit,
+        ]
+        .map(|column| column + 1)
+    }
+
+    fn add_candidate(&mut self, pos: Vec2D, val: usize) {
+        let first_node = self.left.len();
+
+        for (offset, column) in Self::constraint_columns(pos, val).into_iter().enumerate() {
+            let node = first_node + offset;
+
+            self.left.push(first_node + (offset + 3) % 4);
+            self.right.push(first_node + (offset + 1) % 4);
+            self.up.push(self.up[column]);
+            self.down.push(column);
+            self.column.push(column);
+            self.node_candidate.push((pos, val));
+
+            let last_in_column = self.up[column];
+            self.down[last_in_column] = node;
+            self.up[column] = node;
+            self.column_size[column] += 1;
+        }
+    }
+
+    fn cover(&mut self, column: usize) {
+        self.right[self.left[column]] = self.right[column];
+        self.left[self.right[column]] = self.left[column];
+        self.updates += 1;
+
+        let mut row_node = self.down[column];
+        while row_node != column {
+            self.eliminations += 1;
+            let mut node = self.right[row_node];
+            while node != row_node {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.column_size[self.column[node]] -= 1;
+                self.updates += 1;
+                node = self.right[node];
+            }
+            row_node = self.down[row_node];
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let mut row_node = self.up[column];
+        while row_node != column {
+            let mut node = self.left[row_node];
+            while node != row_node {
+                self.column_size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row_node = self.up[row_node];
+        }
+
+        self.right[self.left[column]] = column;
+        self.left[self.right[column]] = column;
+    }
+
+    fn min_size_column(&self) -> Option<usize> {
+        let mut column = self.right[ROOT];
+        let mut min_column = None;
+
+        while column != ROOT {
+            match min_column {
+                Some(chosen) if self.column_size[chosen] <= self.column_size[column] => {}
+                _ => min_column = Some(column),
+            }
+            column = self.right[column];
+        }
+
+        min_column
+    }
+
+    fn search(
+        &mut self,
+        limit: usize,
+        solutions: &mut Vec<GameBoard<BOX_SIZE>>,
+        monitor: &mut SearchMonitor<BOX_SIZE>,
+    ) {
+        self.nodes += 1;
+        if !monitor.visit() {
+            return;
+        }
+        monitor.descended(self.partial_solution.len());
+        monitor.reached(self.partial_solution.len(), || self.partial_gameboard());
+
+        let column = match self.min_size_column() {
+            None => {
+                solutions.push(self.partial_gameboard());
+                return;
+            }
+            Some(column) => column,
+        };
+
+        self.cover(column);
+
+        let mut row_node = self.down[column];
+        while row_node != column && solutions.len() < limit && !monitor.is_interrupted() {
+            self.partial_solution.push(row_node);
+
+            let mut node = self.right[row_node];
+            while node != row_node {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            self.search(limit, solutions, monitor);
+
+            let mut node = self.left[row_node];
+            while node != row_node {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+
+            self.partial_solution.pop();
+            row_node = self.down[row_node];
+            if solutions.len() < limit && !monitor.is_interrupted() {
+                monitor.backtracked();
+            }
+        }
+
+        self.uncover(column);
+    }
+
+    fn partial_gameboard(&self) -> GameBoard<BOX_SIZE> {
+        let mut gameboard = self.gameboard.clone();
+
+        for &node in self.partial_solution.iter() {
+            let (pos, val) = self.node_candidate[node];
+            if gameboard[pos] == Cell::Empty {
+                gameboard[pos] = Cell::Guess(val);
+            }
+        }
+
+        gameboard
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DancingLinks;
+    use crate::{
+        core::{test_fixtures, Cell, GameBoard},
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn finds_unique_solution() {
+        let gameboard = test_fixtures::classic();
+
+        let mut dlx = DancingLinks::build(&gameboard);
+        let solutions = dlx.solve(2);
+
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].as_raw(), test_fixtures::classic_solution());
+        assert!(dlx.nodes() > 0 && dlx.updates() > 0);
+    }
+
+    #[test]
+    fn enumerates_every_4x4_grid() {
+        let mut dlx = DancingLinks::build(&GameBoard::<2>::create_empty());
+        assert_eq!(dlx.solve(usize::MAX).len(), 288);
+    }
+
+    #[test]
+    fn conflicting_givens_have_no_solution() {
+        let mut gameboard = GameBoard::<2>::create_empty();
+        gameboard[Vec2D::new(0, 0)] = Cell::Given(1);
+        gameboard[Vec2D::new(0, 3)] = Cell::Given(1);
+
+        assert!(DancingLinks::build(&gameboard).first_solution().is_none());
+    }
+
+    #[test]
+    fn wrong_guess_has_no_solution() {
+        let mut gameboard = test_fixtures::classic();
+        gameboard[Vec2D::new(0, 1)] = Cell::Guess(9);
+        assert!(gameboard.is_consistent());
+        assert!(DancingLinks::build(&gameboard).first_solution().is_none());
+
+        gameboard[Vec2D::new(0, 1)] = Cell::Guess(1);
+        let solution = DancingLinks::build(&gameboard).first_solution().unwrap();
+        assert!(solution.is_solved());
+        assert_eq!(solution[Vec2D::new(0, 1)], Cell::Guess(1));
+    }
+}
@@ -0,0 +1,153 @@
+use super::{
+    logical_solver::{CandidateGrid, LogicalSolver, SolverOptions, Step, Technique},
+    wave_function_collapse::{WaveFunction, WaveState},
+    GameBoard,
+};
+
+// Puzzles logic cannot finish start above every technique rating.
+const BACKTRACKING_RATING: f64 = 8.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DifficultyLabel {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    Diabolical,
+}
+
+impl DifficultyLabel {
+    fn from_score(score: f64) -> Self {
+        match score {
+            score if score < 2.5 => DifficultyLabel::Easy,
+            score if score < 3.5 => DifficultyLabel::Medium,
+            score if score < 4.5 => DifficultyLabel::Hard,
+            score if score < 6.0 => DifficultyLabel::Expert,
+            _ => DifficultyLabel::Diabolical,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difficulty {
+    pub score: f64,
+    pub label: DifficultyLabel,
+    pub hardest: Option<Technique>,
+    pub steps: usize,
+    /// Dead ends the wave function hit finishing the grid, `None` when logic alone solved it.
+    pub backtracks: Option<u64>,
+}
+
+impl<const BOX_SIZE: usize> GameBoard<BOX_SIZE> {
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty_with(SolverOptions::default())
+    }
+
+    pub fn difficulty_with(&self, options: SolverOptions) -> Difficulty {
+        let mut solver = LogicalSolver::with_options(self.clone(), &Technique::ALL, options);
+        let steps = solver.solve();
+
+        let hardest = steps
+            .iter()
+            .max_by(|first, second| first.rating().total_cmp(&second.rating()));
+        let hardest_rating = hardest.map(Step::rating).unwrap_or_default();
+
+        let backtracks = match solver.grid.is_solved() {
+            true => None,
+            false => Some(wave_function_backtracks(&solver.grid)),
+        };
+        let base_rating = match backtracks {
+            None => hardest_rating,
+            Some(backtracks) => BACKTRACKING_RATING + (1.0 + backtracks as f64).log2() / 2.0,
+        };
+
+        // Longer solve paths nudge the score up, capped below the 0.1 between the closest ratings.
+        let score = base_rating + (steps.len() as f64 / 200.0).min(0.09);
+
+        Difficulty {
+            score,
+            label: DifficultyLabel::from_score(score),
+            hardest: hardest.map(|step| step.technique),
+            steps: steps.len(),
+            backtracks,
+        }
+    }
+}
+
+/// Dead ends met finishing the grid from where logic stalled, keeping its eliminations.
+fn wave_function_backtracks<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> u64 {
+    let mut wave_fn = WaveFunction::with_state(WaveState::from(grid), Default::default());
+    let mut backtracks = 0;
+
+    while !wave_fn.state.entropy_queue.is_empty() || wave_fn.state.is_contradiction() {
+        if !wave_fn.simulate_generation() {
+            if !wave_fn.force_backtrack_prev_frame() {
+                break;
+            }
+            backtracks += 1;
+        }
+    }
+
+    backtracks
+}
+
+#[cfg(test)]
+mod test {
+    use super::DifficultyLabel;
+    use crate::{
+        core::{
+            logical_solver::{Candidate, LogicalSolver, Technique},
+            test_fixtures, GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn singles_only_puzzle_is_easy() {
+        let difficulty = test_fixtures::classic().difficulty();
+
+        assert_eq!(difficulty.label, DifficultyLabel::Easy);
+        assert!(difficulty.hardest <= Some(Technique::NakedSingle));
+        assert_eq!(difficulty.backtracks, None);
+    }
+
+    #[test]
+    fn falls_back_to_backtracking() {
+        let difficulty = test_fixtures::hard().difficulty();
+
+        assert_eq!(difficulty.label, DifficultyLabel::Diabolical);
+        assert!(difficulty.backtracks.is_some());
+    }
+
+    #[test]
+    fn x_wing_is_preferred_over_quads() {
+        let mut solver = LogicalSolver::build(GameBoard::<3>::create_empty());
+        // Digit 1 fits only columns 1 and 7 of rows 0 and 4: an X-Wing.
+        for row_idx in [0, 4] {
+            for col_idx in (0..9).filter(|col_idx| ![1, 7].contains(col_idx)) {
+                solver
+                    .grid
+                    .eliminate(Candidate::new(Vec2D::new(row_idx, col_idx), 1));
+            }
+        }
+        // The first four cells of row 8 hold only 2 to 5: a naked quad.
+        for col_idx in 0..4 {
+            for digit in [1, 6, 7, 8, 9] {
+                solver
+                    .grid
+                    .eliminate(Candidate::new(Vec2D::new(8, col_idx), digit));
+            }
+        }
+
+        let step = solver.next_step().unwrap();
+        assert_eq!(step.technique, Technique::Fish);
+        assert!(step.rating() < Technique::NakedQuad.base_rating());
+    }
+
+    #[test]
+    fn technique_ratings_follow_solving_order() {
+        assert!(Technique::ALL
+            .windows(2)
+            .all(|pair| pair[0] < pair[1] && pair[0].base_rating() <= pair[1].base_rating()));
+    }
+}
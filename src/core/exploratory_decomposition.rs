@@ -0,0 +1,229 @@
+use super::{
+    solver::{GaveUpReason, SearchMonitor, SolverLimits, SolverStats},
+    wave_function_collapse::{Decomposition, WaveState},
+    GameBoard,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+#[derive(Debug)]
+pub struct ExploratoryOutcome<const BOX_SIZE: usize> {
+    pub solution: Option<GameBoard<BOX_SIZE>>,
+    pub time_to_solution: Option<Duration>,
+    pub elapsed: Duration,
+    pub decompositions: u64,
+}
+
+struct TaskPool<const BOX_SIZE: usize> {
+    tasks: Mutex<PendingTasks<BOX_SIZE>>,
+    task_available: Condvar,
+    found_solution: AtomicBool,
+    solution: Mutex<Option<(GameBoard<BOX_SIZE>, Duration)>>,
+    start_time: Instant,
+    limits: SolverLimits,
+    gave_up: Mutex<Option<GaveUpReason>>,
+    decompositions: AtomicU64,
+    dead_ends: AtomicU64,
+    // Filled cell count of the fullest board decomposed so far, with that board.
+    deepest: Mutex<Option<(usize, GameBoard<BOX_SIZE>)>>,
+}
+
+struct PendingTasks<const BOX_SIZE: usize> {
+    queued: Vec<WaveState<BOX_SIZE>>,
+    // Tasks that are either queued or being decomposed by a worker.
+    outstanding: usize,
+}
+
+impl<const BOX_SIZE: usize> WaveState<BOX_SIZE> {
+    pub fn exploratory_decomposition(self, num_threads: usize) -> ExploratoryOutcome<BOX_SIZE> {
+        self.exploratory_decomposition_monitored(num_threads, &mut SearchMonitor::default())
+    }
+
+    pub(super) fn exploratory_decomposition_monitored(
+        self,
+        num_threads: usize,
+        monitor: &mut SearchMonitor<BOX_SIZE>,
+    ) -> ExploratoryOutcome<BOX_SIZE> {
+        // Clashing givens can survive decomposition untouched, so they are ruled out up front.
+        if !self.gameboard.is_consistent() {
+            return ExploratoryOutcome {
+                solution: None,
+                time_to_solution: None,
+                elapsed: Duration::ZERO,
+                decompositions: 0,
+            };
+        }
+
+        let pool = TaskPool {
+            tasks: Mutex::new(PendingTasks {
+                queued: vec![self],
+                outstanding: 1,
+            }),
+            task_available: Condvar::new(),
+            found_solution: AtomicBool::new(false),
+            solution: Mutex::new(None),
+            start_time: Instant::now(),
+            limits: monitor.limits().clone(),
+            gave_up: Mutex::new(None),
+            decompositions: AtomicU64::new(0),
+            dead_ends: AtomicU64::new(0),
+            deepest: Mutex::new(None),
+        };
+
+        std::thread::scope(|scope| {
+            for _ in 0..num_threads.max(1) {
+                scope.spawn(|| pool.worker());
+            }
+        });
+
+        let elapsed = pool.start_time.elapsed();
+        let decompositions = pool.decompositions.into_inner();
+        monitor.record(SolverStats {
+            nodes: decompositions,
+            backtracks: pool.dead_ends.into_inner(),
+            ..SolverStats::default()
+        });
+        if let Some(reason) = pool.gave_up.into_inner().unwrap() {
+            monitor.give_up(reason);
+        }
+        if let Some((filled, gameboard)) = pool.deepest.into_inner().unwrap() {
+            monitor.reached(filled, || gameboard);
+        }
+        let (solution, time_to_solution) = match pool.solution.into_inner().unwrap() {
+            Some((gameboard, time_to_solution)) => (Some(gameboard), Some(time_to_solution)),
+            None => (None, None),
+        };
+
+        ExploratoryOutcome {
+            solution,
+            time_to_solution,
+            elapsed,
+            decompositions,
+        }
+    }
+}
+
+impl<const BOX_SIZE: usize> TaskPool<BOX_SIZE> {
+    fn worker(&self) {
+        while let Some(mut state) = self.next_task() {
+            if self.found_solution.load(Ordering::Acquire) || self.has_given_up() {
+                self.finish_task(Vec::new());
+                continue;
+            }
+            let decompositions = self.decompositions.fetch_add(1, Ordering::Relaxed);
+            if let Some(reason) = self.limits.check(decompositions) {
+                self.decompositions.fetch_sub(1, Ordering::Relaxed);
+                self.give_up(reason);
+                self.finish_task(Vec::new());
+                continue;
+            }
+
+            let decomposition = state.decompose();
+            self.record_progress(&state);
+            match decomposition {
+                Decomposition::Solved => {
+                    self.record_solution(state.gameboard);
+                    self.finish_task(Vec::new());
+                }
+                Decomposition::DeadEnd => {
+                    self.dead_ends.fetch_add(1, Ordering::Relaxed);
+                    self.finish_task(Vec::new());
+                }
+                Decomposition::Branched(branches) => self.finish_task(branches),
+            }
+        }
+    }
+
+    fn next_task(&self) -> Option<WaveState<BOX_SIZE>> {
+        let mut tasks = self.tasks.lock().unwrap();
+        loop {
+            if self.found_solution.load(Ordering::Acquire)
+                || self.has_given_up()
+                || tasks.outstanding == 0
+            {
+                return None;
+            }
+            if let Some(state) = tasks.queued.pop() {
+                return Some(state);
+            }
+            tasks = self.task_available.wait(tasks).unwrap();
+        }
+    }
+
+    fn finish_task(&self, branches: Vec<WaveState<BOX_SIZE>>) {
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.outstanding += branches.len();
+        tasks.outstanding -= 1;
+        // Pushed in reverse so the first candidate is explored first, as in the serial search.
+        tasks.queued.extend(branches.into_iter().rev());
+        self.task_available.notify_all();
+    }
+
+    fn has_given_up(&self) -> bool {
+        self.gave_up.lock().unwrap().is_some()
+    }
+
+    fn give_up(&self, reason: GaveUpReason) {
+        self.gave_up.lock().unwrap().get_or_insert(reason);
+    }
+
+    fn record_progress(&self, state: &WaveState<BOX_SIZE>) {
+        if state.is_contradiction() {
+            return;
+        }
+
+        let board_size = state.gameboard.board_size();
+        let filled = board_size * board_size - state.entropy_queue.len();
+        let mut deepest = self.deepest.lock().unwrap();
+        match *deepest {
+            Some((deepest_filled, _)) if deepest_filled >= filled => {}
+            _ => *deepest = Some((filled, state.gameboard.clone())),
+        }
+    }
+
+    fn record_solution(&self, gameboard: GameBoard<BOX_SIZE>) {
+        let mut solution = self.solution.lock().unwrap();
+        if solution.is_none() {
+            *solution = Some((gameboard, self.start_time.elapsed()));
+            self.found_solution.store(true, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{test_fixtures, wave_function_collapse::WaveState, Cell, GameBoard},
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn finds_solution_on_multiple_threads() {
+        let gameboard = test_fixtures::classic();
+
+        let outcome = WaveState::build(gameboard).exploratory_decomposition(4);
+
+        assert!(outcome.time_to_solution.unwrap() <= outcome.elapsed);
+        assert_eq!(
+            outcome.solution.unwrap().as_raw(),
+            test_fixtures::classic_solution()
+        );
+    }
+
+    #[test]
+    fn clashing_givens_have_no_solution() {
+        let mut gameboard = GameBoard::<2>::create_empty();
+        gameboard[Vec2D::new(0, 0)] = Cell::Given(1);
+        gameboard[Vec2D::new(0, 3)] = Cell::Given(1);
+
+        let outcome = WaveState::build(gameboard).exploratory_decomposition(2);
+
+        assert!(outcome.solution.is_none());
+        assert_eq!(outcome.decompositions, 0);
+    }
+}
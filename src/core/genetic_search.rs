@@ -0,0 +1,326 @@
+use super::{solver::SearchMonitor, stochastic_search::StochasticOutcome, Cell, GameBoard};
+use crate::utils::Vec2D;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// How a child takes its boxes from two parents. Boxes move whole, so each stays a permutation
+/// of its missing digits and the givens are never touched.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Crossover {
+    /// Bands of boxes from whichever parent conflicts less along their rows or columns.
+    #[default]
+    Band,
+    /// Each box from either parent with equal chance.
+    BoxUniform,
+    /// Boxes up to a random cut, in reading order, from one parent and the rest from the other.
+    SinglePoint,
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneticParameters {
+    pub population_size: usize,
+    /// Fittest chromosomes copied unchanged into the next generation.
+    pub elitism: usize,
+    pub tournament_size: usize,
+    pub crossover: Crossover,
+    pub crossover_rate: f64,
+    /// Chance for each box of a child to have two of its free cells swapped.
+    pub mutation_rate: f64,
+    /// Generations without improving the best board before the population is reseeded.
+    pub reseed_after: usize,
+    pub max_generations: usize,
+    pub seed: u64,
+}
+
+impl Default for GeneticParameters {
+    fn default() -> Self {
+        Self {
+            population_size: 100,
+            elitism: 2,
+            tournament_size: 2,
+            crossover: Crossover::default(),
+            crossover_rate: 0.9,
+            mutation_rate: 0.2,
+            reseed_after: 50,
+            max_generations: 20_000,
+            seed: 0,
+        }
+    }
+}
+
+struct Chromosome<const BOX_SIZE: usize> {
+    gameboard: GameBoard<BOX_SIZE>,
+    conflicts: usize,
+}
+
+impl<const BOX_SIZE: usize> Chromosome<BOX_SIZE> {
+    fn build(gameboard: GameBoard<BOX_SIZE>) -> Self {
+        let conflicts = gameboard.conflicts();
+        Self {
+            gameboard,
+            conflicts,
+        }
+    }
+}
+
+impl<const BOX_SIZE: usize> GameBoard<BOX_SIZE> {
+    pub fn genetic_search(&self, parameters: &GeneticParameters) -> StochasticOutcome<BOX_SIZE> {
+        self.genetic_search_monitored(parameters, &mut SearchMonitor::default())
+    }
+
+    pub(super) fn genetic_search_monitored(
+        &self,
+        parameters: &GeneticParameters,
+        monitor: &mut SearchMonitor<BOX_SIZE>,
+    ) -> StochasticOutcome<BOX_SIZE> {
+        let mut rng = StdRng::seed_from_u64(parameters.seed);
+        let free_cells = self.clone().fill_boxes(&mut rng);
+        let population_size = parameters.population_size.max(1);
+        let elitism = parameters.elitism.min(population_size);
+
+        let mut population = self.random_population(population_size, &mut rng);
+        let mut best = Chromosome::build(population[0].gameboard.clone());
+        let mut stale_generations = 0;
+
+        for _ in 0..parameters.max_generations {
+            if best.conflicts == 0 || !monitor.visit() {
+                break;
+            }
+
+            // A converged population rarely escapes its local minimum, so start over from scratch.
+            if stale_generations >= parameters.reseed_after {
+                population = self.random_population(population_size, &mut rng);
+                stale_generations = 0;
+            }
+
+            let mut next_population = population
+                .iter()
+                .take(elitism)
+                .map(|chromosome| Chromosome::build(chromosome.gameboard.clone()))
+                .collect::<Vec<_>>();
+
+            while next_population.len() < population_size {
+                let first_parent = tournament(&population, parameters.tournament_size, &mut rng);
+                let second_parent = tournament(&population, parameters.tournament_size, &mut rng);
+
+                let mut child = match rng.gen::<f64>() < parameters.crossover_rate {
+                    true => first_parent.crossover(second_parent, parameters.crossover, &mut rng),
+                    false => first_parent.gameboard.clone(),
+                };
+                child.mutate(&free_cells, parameters.mutation_rate, &mut rng);
+                next_population.push(Chromosome::build(child));
+            }
+
+            population = next_population;
+            population.sort_by_key(|chromosome| chromosome.conflicts);
+
+            match population[0].conflicts < best.conflicts {
+                true => {
+                    best = Chromosome::build(population[0].gameboard.clone());
+                    stale_generations = 0;
+                }
+                false => stale_generations += 1,
+            }
+        }
+
+        match best.conflicts {
+            0 => StochasticOutcome::Solved(best.gameboard),
+            conflicts => StochasticOutcome::BestEffort {
+                gameboard: best.gameboard,
+                conflicts,
+            },
+        }
+    }
+
+    fn random_population(&self, size: usize, rng: &mut StdRng) -> Vec<Chromosome<BOX_SIZE>> {
+        let mut population = (0..size)
+            .map(|_| self.random_chromosome(rng))
+            .collect::<Vec<_>>();
+        population.sort_by_key(|chromosome| chromosome.conflicts);
+        population
+    }
+
+    fn random_chromosome(&self, rng: &mut StdRng) -> Chromosome<BOX_SIZE> {
+        let mut gameboard = self.clone();
+        gameboard.fill_boxes(rng);
+        Chromosome::build(gameboard)
+    }
+
+    fn mutate(&mut self, free_cells: &[Vec<Vec2D>], mutation_rate: f64, rng: &mut StdRng) {
+        for cells in free_cells.iter().filter(|cells| cells.len() > 1) {
+            if rng.gen::<f64>() >= mutation_rate {
+                continue;
+            }
+
+            // Swaps that would move a digit next to the same given in a row or column are retried.
+            for _ in 0..cells.len() {
+                let mut picked = cells.choose_multiple(rng, 2);
+                let (first, second) = (*picked.next().unwrap(), *picked.next().unwrap());
+                if !self.clashes_with_given(second, self[first].value())
+                    && !self.clashes_with_given(first, self[second].value())
+                {
+                    self.swap_cells(first, second);
+                    break;
+                }
+            }
+        }
+    }
+
+    fn clashes_with_given(&self, pos: Vec2D, num: usize) -> bool {
+        (0..Self::BOARD_SIZE).any(|idx| {
+            self[Vec2D::new(pos.x(), idx)] == Cell::Given(num)
+                || self[Vec2D::new(idx, pos.y())] == Cell::Given(num)
+        })
+    }
+}
+
+impl<const BOX_SIZE: usize> Chromosome<BOX_SIZE> {
+    fn crossover(
+        &self,
+        other: &Self,
+        operator: Crossover,
+        rng: &mut StdRng,
+    ) -> GameBoard<BOX_SIZE> {
+        match operator {
+            Crossover::Band => self.band_crossover(other, rng),
+            Crossover::BoxUniform => self.box_crossover(other, |_| rng.gen::<bool>()),
+            Crossover::SinglePoint => {
+                let cut = rng.gen_range(0..GameBoard::<BOX_SIZE>::BOARD_SIZE);
+                self.box_crossover(other, |box_idx| box_idx > cut)
+            }
+        }
+    }
+
+    // Each band comes from the parent whose rows (or columns) conflict less there.
+    fn band_crossover(&self, other: &Self, rng: &mut StdRng) -> GameBoard<BOX_SIZE> {
+        let by_rows = rng.gen::<bool>();
+        let mut child = self.gameboard.clone();
+
+        for band in 0..BOX_SIZE {
+            let lines = band * BOX_SIZE..(band + 1) * BOX_SIZE;
+            let band_conflicts = |gameboard: &GameBoard<BOX_SIZE>| -> usize {
+                lines
+                    .clone()
+                    .map(|line| match by_rows {
+                        true => gameboard.row_conflicts(line),
+                        false => gameboard.col_conflicts(line),
+                    })
+                    .sum()
+            };
+
+            let (own, others) = (
+                band_conflicts(&self.gameboard),
+                band_conflicts(&other.gameboard),
+            );
+            if others < own || (others == own && rng.gen::<bool>()) {
+                for line in lines.clone() {
+                    for idx in 0..GameBoard::<BOX_SIZE>::BOARD_SIZE {
+                        let pos = match by_rows {
+                            true => Vec2D::new(line, idx),
+                            false => Vec2D::new(idx, line),
+                        };
+                        child[pos] = other.gameboard[pos];
+                    }
+                }
+            }
+        }
+
+        child
+    }
+
+    fn box_crossover(
+        &self,
+        other: &Self,
+        mut from_other: impl FnMut(usize) -> bool,
+    ) -> GameBoard<BOX_SIZE> {
+        let mut child = self.gameboard.clone();
+
+        for box_idx in 0..GameBoard::<BOX_SIZE>::BOARD_SIZE {
+            if from_other(box_idx) {
+                let box_start = GameBoard::<BOX_SIZE>::box_start(box_idx);
+                for pos in other.gameboard.box_cell_positions(box_start) {
+                    child[pos] = other.gameboard[pos];
+                }
+            }
+        }
+
+        child
+    }
+}
+
+fn tournament<'a, const BOX_SIZE: usize>(
+    population: &'a [Chromosome<BOX_SIZE>],
+    tournament_size: usize,
+    rng: &mut StdRng,
+) -> &'a Chromosome<BOX_SIZE> {
+    (0..tournament_size.max(1))
+        .map(|_| &population[rng.gen_range(0..population.len())])
+        .min_by_key(|chromosome| chromosome.conflicts)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Crossover, GeneticParameters};
+    use crate::{
+        core::{stochastic_search::StochasticOutcome, test_fixtures, Cell, GameBoard},
+        utils::Vec2D,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn genetic_search_solves_grid() {
+        let gameboard = test_fixtures::classic();
+
+        for crossover in [
+            Crossover::Band,
+            Crossover::BoxUniform,
+            Crossover::SinglePoint,
+        ] {
+            let parameters = GeneticParameters {
+                crossover,
+                ..GeneticParameters::default()
+            };
+            match gameboard.genetic_search(&parameters) {
+                StochasticOutcome::Solved(solution) => assert!(solution.is_solved()),
+                StochasticOutcome::BestEffort { conflicts, .. } => {
+                    panic!("{:?} stopped with {} conflicts", crossover, conflicts)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_crossover_keeps_givens_and_boxes() {
+        let gameboard = test_fixtures::classic();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for operator in [
+            Crossover::Band,
+            Crossover::BoxUniform,
+            Crossover::SinglePoint,
+        ] {
+            for _ in 0..20 {
+                let first = gameboard.random_chromosome(&mut rng);
+                let second = gameboard.random_chromosome(&mut rng);
+                let child = first.crossover(&second, operator, &mut rng);
+
+                for i in 0..9 {
+                    for j in 0..9 {
+                        let pos = Vec2D::new(i, j);
+                        if let Cell::Given(_) = gameboard[pos] {
+                            assert_eq!(child[pos], gameboard[pos]);
+                        }
+                    }
+                }
+                for box_idx in 0..9 {
+                    let mut digits = child
+                        .box_cell_positions(GameBoard::<3>::box_start(box_idx))
+                        .map(|pos| child[pos].value())
+                        .collect::<Vec<_>>();
+                    digits.sort();
+                    assert_eq!(digits, (1..=9).collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+}
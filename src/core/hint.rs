@@ -0,0 +1,142 @@
+use super::{
+    dancing_links::DancingLinks,
+    logical_solver::{explanation::cell_name, LogicalSolver, SolverOptions, Step, Technique},
+    Cell, GameBoard,
+};
+use crate::utils::Vec2D;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HintKind {
+    Step(Step),
+    Mistake,
+    Solved,
+    Unsolvable,
+    Stuck,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hint {
+    pub kind: HintKind,
+    pub highlight: Vec<Vec2D>,
+    pub explanation: String,
+}
+
+impl<const BOX_SIZE: usize> GameBoard<BOX_SIZE> {
+    pub fn hint(&self) -> Hint {
+        let givens = self.givens();
+        let solutions = DancingLinks::build(&givens).solve(2);
+
+        let mistakes = match solutions.as_slice() {
+            [] => {
+                return Hint {
+                    kind: HintKind::Unsolvable,
+                    highlight: Vec::new(),
+                    explanation: "The givens have no solution".to_string(),
+                }
+            }
+            [solution] => self
+                .guesses()
+                .filter(|&pos| self[pos] != solution[pos])
+                .collect(),
+            // Without a unique solution a guess is only wrong once the guesses contradict each other.
+            _ => match DancingLinks::build(&self.fixed_guesses()).first_solution() {
+                Some(_) => Vec::new(),
+                None => self.guesses().collect(),
+            },
+        };
+        if !mistakes.is_empty() {
+            let explanation = match (solutions.len(), mistakes.as_slice()) {
+                (1, [pos]) => format!("Mistake: {} is wrong", cell_name(*pos)),
+                (1, _) => format!("Mistake: {} cells are wrong", mistakes.len()),
+                _ => "Mistake: the entered digits cannot all be right".to_string(),
+            };
+            return Hint {
+                kind: HintKind::Mistake,
+                highlight: mistakes,
+                explanation,
+            };
+        }
+
+        if self.is_solved() {
+            return Hint {
+                kind: HintKind::Solved,
+                highlight: Vec::new(),
+                explanation: "The puzzle is solved".to_string(),
+            };
+        }
+
+        let options = SolverOptions {
+            assume_uniqueness: solutions.len() == 1,
+            ..SolverOptions::default()
+        };
+        match LogicalSolver::with_options(self.clone(), &Technique::ALL, options).next_step() {
+            Some(step) => Hint {
+                highlight: step.cells.clone(),
+                explanation: step.to_string(),
+                kind: HintKind::Step(step),
+            },
+            None => Hint {
+                kind: HintKind::Stuck,
+                highlight: Vec::new(),
+                explanation: "No logical step is available".to_string(),
+            },
+        }
+    }
+
+    fn guesses(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        let board_size = self.board_size();
+        (0..board_size)
+            .flat_map(move |i| (0..board_size).map(move |j| Vec2D::new(i, j)))
+            .filter(|&pos| matches!(self[pos], Cell::Guess(_)))
+    }
+
+    fn givens(&self) -> Self {
+        let mut givens = self.clone();
+        for pos in self.guesses() {
+            givens[pos] = Cell::Empty;
+        }
+        givens
+    }
+
+    fn fixed_guesses(&self) -> Self {
+        let mut fixed = self.clone();
+        for pos in self.guesses() {
+            fixed[pos] = Cell::Given(self[pos].value());
+        }
+        fixed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HintKind;
+    use crate::{
+        core::{test_fixtures, Cell, GameBoard},
+        utils::Vec2D,
+    };
+
+    fn puzzle() -> GameBoard<3> {
+        test_fixtures::classic()
+    }
+
+    #[test]
+    fn hints_easiest_step() {
+        let hint = puzzle().hint();
+
+        assert!(matches!(hint.kind, HintKind::Step(_)));
+        assert!(!hint.highlight.is_empty());
+        assert!(hint.explanation.starts_with("Hidden single: "));
+    }
+
+    #[test]
+    fn points_out_wrong_guess() {
+        let mut gameboard = puzzle();
+        gameboard[Vec2D::new(0, 1)] = Cell::Guess(9);
+
+        let hint = gameboard.hint();
+
+        assert_eq!(hint.kind, HintKind::Mistake);
+        assert_eq!(hint.highlight, vec![Vec2D::new(0, 1)]);
+        assert_eq!(hint.explanation, "Mistake: r1c2 is wrong");
+    }
+}
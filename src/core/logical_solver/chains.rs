@@ -0,0 +1,300 @@
+use super::{Candidate, CandidateGrid, Step, StepDetail, Technique};
+use std::collections::{HashMap, VecDeque};
+
+const MAX_CHAIN_LINKS: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Link {
+    Strong,
+    Weak,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainLink {
+    pub from: Candidate,
+    pub to: Candidate,
+    pub link: Link,
+}
+
+pub fn x_chain<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    LinkGraph::build(grid, true).find_chain(grid, Technique::XChain)
+}
+
+pub fn aic<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    LinkGraph::build(grid, false).find_chain(grid, Technique::Aic)
+}
+
+struct LinkGraph {
+    candidates: Vec<Candidate>,
+    strong_links: Vec<Vec<usize>>,
+    weak_links: Vec<Vec<usize>>,
+}
+
+impl LinkGraph {
+    fn build<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>, single_digit: bool) -> Self {
+        let candidates = grid
+            .empty_cells()
+            .flat_map(|pos| {
+                grid.candidates(pos)
+                    .iter()
+                    .map(move |digit| Candidate::new(pos, digit))
+            })
+            .collect::<Vec<_>>();
+        let index = candidates
+            .iter()
+            .enumerate()
+            .map(|(idx, &candidate)| (candidate, idx))
+            .collect::<HashMap<_, _>>();
+
+        let mut strong_links = vec![Vec::new(); candidates.len()];
+        let mut weak_links = vec![Vec::new(); candidates.len()];
+
+        for (idx, &Candidate { pos, digit }) in candidates.iter().enumerate() {
+            if !single_digit {
+                let cell_tiles = grid.candidates(pos);
+                for other_digit in cell_tiles.iter().filter(|&other| other != digit) {
+                    let other = index[&Candidate::new(pos, other_digit)];
+                    weak_links[idx].push(other);
+                    if cell_tiles.len() == 2 {
+                        strong_links[idx].push(other);
+                    }
+                }
+            }
+
+            for peer in grid.peers(pos) {
+                if let Some(&other) = index.get(&Candidate::new(peer, digit)) {
+                    weak_links[idx].push(other);
+                }
+            }
+
+            for house in grid.houses() {
+                let places = grid
+                    .house_cells(house)
+                    .into_iter()
+                    .filter(|&cell| grid.candidates(cell).contains(digit))
+                    .collect::<Vec<_>>();
+                if places.len() == 2 && places.contains(&pos) {
+                    let other_pos = if places[0] == pos {
+                        places[1]
+                    } else {
+                        places[0]
+                    };
+                    let other = index[&Candidate::new(other_pos, digit)];
+                    if !strong_links[idx].contains(&other) {
+                        strong_links[idx].push(other);
+                    }
+                }
+            }
+        }
+
+        Self {
+            candidates,
+            strong_links,
+            weak_links,
+        }
+    }
+
+    fn find_chain<const BOX_SIZE: usize>(
+        &self,
+        grid: &CandidateGrid<BOX_SIZE>,
+        technique: Technique,
+    ) -> Option<Step> {
+        (0..self.candidates.len()).find_map(|start| self.search_from(grid, technique, start))
+    }
+
+    // Breadth-first search over alternating chains that start and end with a strong link.
+    fn search_from<const BOX_SIZE: usize>(
+        &self,
+        grid: &CandidateGrid<BOX_SIZE>,
+        technique: Technique,
+        start: usize,
+    ) -> Option<Step> {
+        let mut parents = HashMap::new();
+        let mut queue = VecDeque::from([(start, Link::Strong, 0)]);
+
+        while let Some((node, next_link, num_links)) = queue.pop_front() {
+            if num_links == MAX_CHAIN_LINKS {
+                continue;
+            }
+            let neighbours = match next_link {
+                Link::Strong => &self.strong_links[node],
+                Link::Weak => &self.weak_links[node],
+            };
+            let following_link = match next_link {
+                Link::Strong => Link::Weak,
+                Link::Weak => Link::Strong,
+            };
+
+            for &neighbour in neighbours {
+                if neighbour == start || parents.contains_key(&(neighbour, following_link)) {
+                    continue;
+                }
+                parents.insert((neighbour, following_link), (node, next_link));
+
+                if next_link == Link::Strong && num_links >= 2 {
+                    let links = self.reconstruct(&parents, start, neighbour);
+                    if let Some(step) = self.chain_step(grid, technique, links) {
+                        return Some(step);
+                    }
+                }
+                queue.push_back((neighbour, following_link, num_links + 1));
+            }
+        }
+
+        None
+    }
+
+    fn reconstruct(
+        &self,
+        parents: &HashMap<(usize, Link), (usize, Link)>,
+        start: usize,
+        end: usize,
+    ) -> Vec<ChainLink> {
+        let mut links = Vec::new();
+        let mut current = (end, Link::Weak);
+
+        while current.0 != start {
+            let (parent, link) = parents[&current];
+            links.push(ChainLink {
+                from: self.candidates[parent],
+                to: self.candidates[current.0],
+                link,
+            });
+            current = (parent, link);
+        }
+
+        links.reverse();
+        links
+    }
+
+    fn chain_step<const BOX_SIZE: usize>(
+        &self,
+        grid: &CandidateGrid<BOX_SIZE>,
+        technique: Technique,
+        links: Vec<ChainLink>,
+    ) -> Option<Step> {
+        // A chain that revisits a candidate is not a valid alternating inference chain.
+        let mut chain_candidates = links.iter().map(|link| link.from).collect::<Vec<_>>();
+        chain_candidates.push(links.last()?.to);
+        if (1..chain_candidates.len())
+            .any(|idx| chain_candidates[..idx].contains(&chain_candidates[idx]))
+        {
+            return None;
+        }
+
+        let (first, last) = (links.first()?.from, links.last()?.to);
+        let eliminations = if first.digit == last.digit {
+            grid.empty_cells()
+                .filter(|&pos| pos != first.pos && pos != last.pos)
+                .filter(|&pos| grid.sees(pos, first.pos) && grid.sees(pos, last.pos))
+                .map(|pos| Candidate::new(pos, first.digit))
+                .filter(|&candidate| grid.has_candidate(candidate))
+                .collect::<Vec<_>>()
+        } else if first.pos == last.pos {
+            grid.candidates(first.pos)
+                .iter()
+                .filter(|&digit| digit != first.digit && digit != last.digit)
+                .map(|digit| Candidate::new(first.pos, digit))
+                .collect()
+        } else if grid.sees(first.pos, last.pos) {
+            [
+                Candidate::new(first.pos, last.digit),
+                Candidate::new(last.pos, first.digit),
+            ]
+            .into_iter()
+            .filter(|&candidate| grid.has_candidate(candidate))
+            .collect()
+        } else {
+            Vec::new()
+        };
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        // AICs can pass through a cell once per digit, but each cell is listed once.
+        let mut cells = Vec::new();
+        for pos in chain_candidates.iter().map(|candidate| candidate.pos) {
+            if !cells.contains(&pos) {
+                cells.push(pos);
+            }
+        }
+        Some(Step {
+            technique,
+            cells,
+            placements: Vec::new(),
+            eliminations,
+            detail: StepDetail::Chain(links),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChainLink, Link, LinkGraph};
+    use crate::{
+        core::{
+            logical_solver::{Candidate, CandidateGrid, StepDetail, Technique},
+            GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn x_chain_alternates_strong_and_weak_links() {
+        let mut grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        let keep_digit_in = |grid: &mut CandidateGrid<3>, row_idx: usize, cols: [usize; 2]| {
+            for col_idx in (0..9).filter(|col_idx| !cols.contains(col_idx)) {
+                grid.eliminate(Candidate::new(Vec2D::new(row_idx, col_idx), 1));
+            }
+        };
+        // Skyscraper on digit 1: conjugate rows 0 and 3 sharing column 1.
+        keep_digit_in(&mut grid, 0, [1, 4]);
+        keep_digit_in(&mut grid, 3, [1, 5]);
+
+        let step = super::x_chain(&grid).unwrap();
+
+        match step.detail {
+            StepDetail::Chain(links) => {
+                assert!(links.len() >= 3);
+                assert_eq!(links.first().unwrap().link, Link::Strong);
+                assert_eq!(links.last().unwrap().link, Link::Strong);
+                assert!(links.iter().all(|link| link.from.digit == 1));
+                assert!(links.windows(2).all(|pair| pair[0].link != pair[1].link));
+            }
+            detail => panic!("unexpected detail {:?}", detail),
+        }
+        assert!(step
+            .eliminations
+            .iter()
+            .all(|candidate| candidate.digit == 1));
+    }
+
+    #[test]
+    fn chain_through_a_cell_twice_lists_it_once() {
+        let grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        let (pivot, other) = (Vec2D::new(0, 0), Vec2D::new(0, 4));
+        let links = vec![
+            ChainLink {
+                from: Candidate::new(pivot, 1),
+                to: Candidate::new(other, 1),
+                link: Link::Strong,
+            },
+            ChainLink {
+                from: Candidate::new(other, 1),
+                to: Candidate::new(other, 2),
+                link: Link::Weak,
+            },
+            ChainLink {
+                from: Candidate::new(other, 2),
+                to: Candidate::new(pivot, 2),
+                link: Link::Strong,
+            },
+        ];
+
+        let step = LinkGraph::build(&grid, false)
+            .chain_step(&grid, Technique::Aic, links)
+            .unwrap();
+
+        assert_eq!(step.cells, vec![pivot, other]);
+    }
+}
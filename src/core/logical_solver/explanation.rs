@@ -0,0 +1,268 @@
+use super::{chains::Link, Candidate, House, Step, StepDetail, Technique};
+use crate::utils::Vec2D;
+use std::fmt;
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Technique::FullHouse => "Full house",
+            Technique::HiddenSingle => "Hidden single",
+            Technique::NakedSingle => "Naked single",
+            Technique::Pointing => "Pointing",
+            Technique::Claiming => "Claiming",
+            Technique::NakedPair => "Naked pair",
+            Technique::HiddenPair => "Hidden pair",
+            Technique::NakedTriple => "Naked triple",
+            Technique::HiddenTriple => "Hidden triple",
+            Technique::NakedQuad => "Naked quad",
+            Technique::HiddenQuad => "Hidden quad",
+            Technique::Fish => "Fish",
+            Technique::FinnedFish => "Finned fish",
+            Technique::UniqueRectangle => "Unique rectangle",
+            Technique::Bug => "BUG+1",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::XChain => "X-Chain",
+            Technique::Aic => "Alternating inference chain",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for House {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            House::Row(idx) => write!(f, "row {}", idx + 1),
+            House::Col(idx) => write!(f, "column {}", idx + 1),
+            House::Box(idx) => write!(f, "box {}", idx + 1),
+        }
+    }
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.digit, cell_name(self.pos))
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match &self.detail {
+            StepDetail::Cell => match self.technique {
+                Technique::Bug => format!(
+                    "every other unsolved cell has two candidates, so assuming a unique solution {}",
+                    self.outcome()
+                ),
+                _ => self.outcome(),
+            },
+            StepDetail::House(house) => match (self.technique, self.placements.first()) {
+                (Technique::FullHouse, Some(placement)) => format!(
+                    "{} is the last empty cell in {}, so it must be {}",
+                    cell_name(placement.pos),
+                    house,
+                    placement.digit
+                ),
+                (_, Some(placement)) => format!(
+                    "{} can only go in {} in {}",
+                    placement.digit,
+                    cell_name(placement.pos),
+                    house
+                ),
+                (_, None) => self.outcome(),
+            },
+            StepDetail::Subset { house, digits } => {
+                let digits = join(digits.iter().map(|digit| digit.to_string()));
+                match self.technique {
+                    Technique::HiddenPair | Technique::HiddenTriple | Technique::HiddenQuad => {
+                        format!(
+                            "{} only fit in {} in {}, so {}",
+                            digits,
+                            cell_list(&self.cells),
+                            house,
+                            self.outcome()
+                        )
+                    }
+                    _ => format!(
+                        "{} in {} can only hold {}, so {}",
+                        cell_list(&self.cells),
+                        house,
+                        digits,
+                        self.outcome()
+                    ),
+                }
+            }
+            StepDetail::Intersection {
+                box_house,
+                line,
+                digit,
+            } => {
+                let (confining, confined) = match self.technique {
+                    Technique::Claiming => (line, box_house),
+                    _ => (box_house, line),
+                };
+                format!(
+                    "in {}, {} is confined to {}, so {}",
+                    confining,
+                    digit,
+                    confined,
+                    self.outcome()
+                )
+            }
+            StepDetail::Fish {
+                digit,
+                size,
+                base,
+                cover,
+                fins,
+            } => {
+                let fins = match fins.is_empty() {
+                    true => String::new(),
+                    false => format!(" with fins in {}", cell_list(fins)),
+                };
+                format!(
+                    "{} on {} in {} covered by {}{}, so {}",
+                    fish_name(*size),
+                    digit,
+                    join(base.iter().map(|house| house.to_string())),
+                    join(cover.iter().map(|house| house.to_string())),
+                    fins,
+                    self.outcome()
+                )
+            }
+            StepDetail::Wing {
+                pivots,
+                pincers,
+                digit,
+            } => format!(
+                "{} and pincers {} on {}, so {}",
+                match self.technique {
+                    Technique::WWing => format!("strong link in {}", cell_list(pivots)),
+                    _ => format!("pivot {}", cell_list(pivots)),
+                },
+                cell_list(pincers),
+                digit,
+                self.outcome()
+            ),
+            StepDetail::UniqueRectangle {
+                kind,
+                corners,
+                digits,
+            } => format!(
+                "type {} on {} in {} would be a deadly pattern, so assuming a unique solution {}",
+                kind,
+                join(digits.iter().map(|digit| digit.to_string())),
+                cell_list(corners),
+                self.outcome()
+            ),
+            StepDetail::Chain(links) => {
+                let chain = links
+                    .iter()
+                    .map(|chain_link| {
+                        let link = match chain_link.link {
+                            Link::Strong => "=",
+                            Link::Weak => "-",
+                        };
+                        format!(" {} {}", link, chain_link.to)
+                    })
+                    .collect::<String>();
+                let start = links
+                    .first()
+                    .map(|chain_link| chain_link.from.to_string())
+                    .unwrap_or_default();
+                format!("{}{}, so {}", start, chain, self.outcome())
+            }
+        };
+
+        write!(f, "{}: {}", self.technique, reason)
+    }
+}
+
+impl Step {
+    fn outcome(&self) -> String {
+        let placements = self
+            .placements
+            .iter()
+            .map(|placement| format!("{} must be {}", cell_name(placement.pos), placement.digit));
+
+        let mut digits = self
+            .eliminations
+            .iter()
+            .map(|elimination| elimination.digit)
+            .collect::<Vec<_>>();
+        digits.sort();
+        digits.dedup();
+        let eliminations = digits.into_iter().map(|digit| {
+            let cells = self
+                .eliminations
+                .iter()
+                .filter(|elimination| elimination.digit == digit)
+                .map(|elimination| elimination.pos)
+                .collect::<Vec<_>>();
+            format!("{} can be removed from {}", digit, cell_list(&cells))
+        });
+
+        placements
+            .chain(eliminations)
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+fn fish_name(size: usize) -> String {
+    match size {
+        2 => "X-Wing".to_string(),
+        3 => "Swordfish".to_string(),
+        4 => "Jellyfish".to_string(),
+        _ => format!("size {} fish", size),
+    }
+}
+
+pub fn cell_name(pos: Vec2D) -> String {
+    format!("r{}c{}", pos.x() + 1, pos.y() + 1)
+}
+
+fn cell_list(cells: &[Vec2D]) -> String {
+    join(cells.iter().map(|&pos| cell_name(pos)))
+}
+
+fn join(items: impl Iterator<Item = String>) -> String {
+    let items = items.collect::<Vec<_>>();
+    match items.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            logical_solver::{singles, CandidateGrid},
+            GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn explains_hidden_single() {
+        let mut gameboard = GameBoard::<3>::create_empty();
+        for (pos, digit) in [
+            (Vec2D::new(3, 3), 7),
+            (Vec2D::new(5, 7), 7),
+            (Vec2D::new(0, 0), 7),
+            (Vec2D::new(7, 2), 7),
+        ] {
+            gameboard[pos] = crate::core::Cell::Given(digit);
+        }
+        let grid = CandidateGrid::build(gameboard);
+
+        let step = singles::hidden_single(&grid).unwrap();
+
+        assert_eq!(
+            step.to_string(),
+            "Hidden single: 7 can only go in r5c2 in row 5"
+        );
+    }
+}
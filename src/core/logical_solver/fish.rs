@@ -0,0 +1,252 @@
+use super::{combinations, Candidate, CandidateGrid, House, Step, StepDetail, Technique};
+use crate::utils::Vec2D;
+
+pub fn basic_fish<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    max_size: usize,
+) -> Option<Step> {
+    (2..=max_size).find_map(|size| find_fish(grid, size, false))
+}
+
+pub fn finned_fish<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    max_size: usize,
+) -> Option<Step> {
+    (2..=max_size).find_map(|size| find_fish(grid, size, true))
+}
+
+#[derive(Clone, Copy)]
+enum Orientation {
+    RowBased,
+    ColBased,
+}
+
+impl Orientation {
+    fn base_house(&self, idx: usize) -> House {
+        match self {
+            Orientation::RowBased => House::Row(idx),
+            Orientation::ColBased => House::Col(idx),
+        }
+    }
+
+    fn cover_house(&self, idx: usize) -> House {
+        match self {
+            Orientation::RowBased => House::Col(idx),
+            Orientation::ColBased => House::Row(idx),
+        }
+    }
+
+    fn cell(&self, base_idx: usize, cover_idx: usize) -> Vec2D {
+        match self {
+            Orientation::RowBased => Vec2D::new(base_idx, cover_idx),
+            Orientation::ColBased => Vec2D::new(cover_idx, base_idx),
+        }
+    }
+}
+
+fn find_fish<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    size: usize,
+    finned: bool,
+) -> Option<Step> {
+    let board_size = grid.board_size();
+
+    (1..=board_size).find_map(|digit| {
+        [Orientation::RowBased, Orientation::ColBased]
+            .into_iter()
+            .find_map(|orientation| {
+                // Bitmask of the cover lines each base line has `digit` in.
+                let line_masks = (0..board_size)
+                    .map(|base_idx| {
+                        (0..board_size)
+                            .filter(|&cover_idx| {
+                                grid.candidates(orientation.cell(base_idx, cover_idx))
+                                    .contains(digit)
+                            })
+                            .fold(0u64, |mask, cover_idx| mask | 1 << cover_idx)
+                    })
+                    .collect::<Vec<_>>();
+
+                let base_lines = (0..board_size)
+                    .filter(
+                        |&base_idx| match line_masks[base_idx].count_ones() as usize {
+                            0 => false,
+                            count => finned || count <= size,
+                        },
+                    )
+                    .collect::<Vec<_>>();
+
+                combinations(&base_lines, size)
+                    .into_iter()
+                    .find_map(|base| {
+                        let union = base.iter().fold(0, |mask, &idx| mask | line_masks[idx]);
+                        let cover_lines = (0..board_size)
+                            .filter(|&idx| union & 1 << idx != 0)
+                            .collect::<Vec<_>>();
+
+                        match (finned, cover_lines.len()) {
+                            (false, count) if count == size => {
+                                fish_step(grid, orientation, digit, &base, &cover_lines, &[])
+                            }
+                            (true, count) if count > size => combinations(&cover_lines, size)
+                                .into_iter()
+                                .find_map(|cover| {
+                                    let fins = base
+                                        .iter()
+                                        .flat_map(|&base_idx| {
+                                            cover_lines
+                                                .iter()
+                                                .filter(|idx| !cover.contains(idx))
+                                                .map(move |&cover_idx| {
+                                                    orientation.cell(base_idx, cover_idx)
+                                                })
+                                        })
+                                        .filter(|&pos| grid.candidates(pos).contains(digit))
+                                        .collect::<Vec<_>>();
+
+                                    fish_step(grid, orientation, digit, &base, &cover, &fins)
+                                }),
+                            _ => None,
+                        }
+                    })
+            })
+    })
+}
+
+fn fish_step<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    orientation: Orientation,
+    digit: usize,
+    base: &[usize],
+    cover: &[usize],
+    fins: &[Vec2D],
+) -> Option<Step> {
+    let fin_box = match fins.first() {
+        None => None,
+        Some(&fin) => {
+            let fin_box = grid.box_index(fin);
+            if fins.iter().any(|&pos| grid.box_index(pos) != fin_box) {
+                return None;
+            }
+            Some(fin_box)
+        }
+    };
+
+    let eliminations = cover
+        .iter()
+        .flat_map(|&cover_idx| {
+            (0..grid.board_size())
+                .filter(|base_idx| !base.contains(base_idx))
+                .map(move |base_idx| orientation.cell(base_idx, cover_idx))
+        })
+        .filter(|&pos| match fin_box {
+            None => true,
+            Some(fin_box) => grid.box_index(pos) == fin_box,
+        })
+        .map(|pos| Candidate::new(pos, digit))
+        .filter(|&candidate| grid.has_candidate(candidate))
+        .collect::<Vec<_>>();
+    if eliminations.is_empty() {
+        return None;
+    }
+
+    let cells = base
+        .iter()
+        .flat_map(|&base_idx| {
+            cover
+                .iter()
+                .map(move |&cover_idx| orientation.cell(base_idx, cover_idx))
+        })
+        .filter(|&pos| grid.candidates(pos).contains(digit))
+        .collect();
+
+    Some(Step {
+        technique: match fin_box {
+            None => Technique::Fish,
+            Some(_) => Technique::FinnedFish,
+        },
+        cells,
+        placements: Vec::new(),
+        eliminations,
+        detail: StepDetail::Fish {
+            digit,
+            size: base.len(),
+            base: base
+                .iter()
+                .map(|&idx| orientation.base_house(idx))
+                .collect(),
+            cover: cover
+                .iter()
+                .map(|&idx| orientation.cover_house(idx))
+                .collect(),
+            fins: fins.to_vec(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            logical_solver::{Candidate, CandidateGrid, House, StepDetail, Technique},
+            GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    fn grid_with_digit_in(places: &[Vec<usize>]) -> CandidateGrid<3> {
+        let mut grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        for (row_idx, cols) in places.iter().enumerate() {
+            if cols.is_empty() {
+                continue;
+            }
+            for col_idx in (0..9).filter(|col_idx| !cols.contains(col_idx)) {
+                grid.eliminate(Candidate::new(Vec2D::new(row_idx, col_idx), 1));
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn finds_x_wing() {
+        let mut places = vec![Vec::new(); 9];
+        places[0] = vec![1, 7];
+        places[4] = vec![1, 7];
+        let grid = grid_with_digit_in(&places);
+
+        let step = super::basic_fish(&grid, 4).unwrap();
+
+        assert_eq!(step.technique, Technique::Fish);
+        assert_eq!(
+            step.detail,
+            StepDetail::Fish {
+                digit: 1,
+                size: 2,
+                base: vec![House::Row(0), House::Row(4)],
+                cover: vec![House::Col(1), House::Col(7)],
+                fins: Vec::new(),
+            }
+        );
+        assert_eq!(step.eliminations.len(), 14);
+    }
+
+    #[test]
+    fn finned_x_wing_only_eliminates_in_fin_box() {
+        let mut places = vec![Vec::new(); 9];
+        places[0] = vec![1, 7];
+        places[4] = vec![1, 7, 8];
+        let grid = grid_with_digit_in(&places);
+
+        assert!(super::basic_fish(&grid, 2).is_none());
+        let step = super::finned_fish(&grid, 2).unwrap();
+
+        assert_eq!(step.technique, Technique::FinnedFish);
+        assert_eq!(
+            step.eliminations,
+            vec![
+                Candidate::new(Vec2D::new(3, 7), 1),
+                Candidate::new(Vec2D::new(5, 7), 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use super::{Candidate, CandidateGrid, House, Step, StepDetail, Technique};
+use crate::utils::Vec2D;
+
+pub fn pointing<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    (0..grid.board_size()).find_map(|box_idx| {
+        let box_cells = grid.house_cells(House::Box(box_idx));
+
+        (1..=grid.board_size()).find_map(|digit| {
+            let cells = digit_places(grid, &box_cells, digit);
+            let first = *cells.first()?;
+
+            [House::Row(first.x()), House::Col(first.y())]
+                .into_iter()
+                .filter(|&line| cells.iter().all(|&pos| line_contains(line, pos)))
+                .find_map(|line| {
+                    let eliminations = grid
+                        .house_cells(line)
+                        .into_iter()
+                        .filter(|pos| !box_cells.contains(pos))
+                        .map(|pos| Candidate::new(pos, digit))
+                        .filter(|&candidate| grid.has_candidate(candidate))
+                        .collect::<Vec<_>>();
+
+                    locked_candidates_step(
+                        Technique::Pointing,
+                        House::Box(box_idx),
+                        line,
+                        digit,
+                        cells.clone(),
+                        eliminations,
+                    )
+                })
+        })
+    })
+}
+
+pub fn claiming<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    let lines = (0..grid.board_size())
+        .map(House::Row)
+        .chain((0..grid.board_size()).map(House::Col))
+        .collect::<Vec<_>>();
+
+    lines.into_iter().find_map(|line| {
+        let line_cells = grid.house_cells(line);
+
+        (1..=grid.board_size()).find_map(|digit| {
+            let cells = digit_places(grid, &line_cells, digit);
+            let box_pos = grid.gameboard.box_position(*cells.first()?);
+            if cells
+                .iter()
+                .any(|&pos| grid.gameboard.box_position(pos) != box_pos)
+            {
+                return None;
+            }
+
+            let eliminations = grid
+                .gameboard
+                .box_cell_positions(box_pos)
+                .filter(|&pos| !line_contains(line, pos))
+                .map(|pos| Candidate::new(pos, digit))
+                .filter(|&candidate| grid.has_candidate(candidate))
+                .collect::<Vec<_>>();
+
+            locked_candidates_step(
+                Technique::Claiming,
+                House::Box(grid.box_index(box_pos)),
+                line,
+                digit,
+                cells,
+                eliminations,
+            )
+        })
+    })
+}
+
+fn digit_places<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    cells: &[Vec2D],
+    digit: usize,
+) -> Vec<Vec2D> {
+    cells
+        .iter()
+        .copied()
+        .filter(|&pos| grid.candidates(pos).contains(digit))
+        .collect()
+}
+
+fn line_contains(line: House, pos: Vec2D) -> bool {
+    match line {
+        House::Row(row_idx) => pos.x() == row_idx,
+        House::Col(col_idx) => pos.y() == col_idx,
+        House::Box(_) => unreachable!(),
+    }
+}
+
+fn locked_candidates_step(
+    technique: Technique,
+    box_house: House,
+    line: House,
+    digit: usize,
+    cells: Vec<Vec2D>,
+    eliminations: Vec<Candidate>,
+) -> Option<Step> {
+    if eliminations.is_empty() {
+        return None;
+    }
+
+    Some(Step {
+        technique,
+        cells,
+        placements: Vec::new(),
+        eliminations,
+        detail: StepDetail::Intersection {
+            box_house,
+            line,
+            digit,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            logical_solver::{Candidate, CandidateGrid, House, StepDetail},
+            GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn explains_pointing_and_claiming() {
+        let mut grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        for pos in [Vec2D::new(1, 0), Vec2D::new(1, 1), Vec2D::new(1, 2)] {
+            grid.eliminate(Candidate::new(pos, 5));
+        }
+        for pos in [Vec2D::new(2, 0), Vec2D::new(2, 1), Vec2D::new(2, 2)] {
+            grid.eliminate(Candidate::new(pos, 5));
+        }
+
+        let step = super::pointing(&grid).unwrap();
+        assert_eq!(
+            step.detail,
+            StepDetail::Intersection {
+                box_house: House::Box(0),
+                line: House::Row(0),
+                digit: 5,
+            }
+        );
+        assert_eq!(step.eliminations.len(), 6);
+
+        let mut grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        for col_idx in (0..3).chain(6..9) {
+            grid.eliminate(Candidate::new(Vec2D::new(0, col_idx), 5));
+        }
+
+        let step = super::claiming(&grid).unwrap();
+        assert_eq!(step.eliminations.len(), 6);
+        assert_eq!(
+            step.detail,
+            StepDetail::Intersection {
+                box_house: House::Box(1),
+                line: House::Row(0),
+                digit: 5,
+            }
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use super::{wave_function_collapse::CellTile, wave_function_collapse::WaveState, Cell, GameBoard};
+use crate::utils::Vec2D;
+
+pub mod chains;
+pub mod explanation;
+pub mod fish;
+pub mod intersections;
+pub mod singles;
+pub mod subsets;
+pub mod uniqueness;
+pub mod wings;
+
+/// Ordered by [`Technique::base_rating`], so the solver tries the easier techniques first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Technique {
+    FullHouse,
+    HiddenSingle,
+    NakedSingle,
+    Pointing,
+    Claiming,
+    NakedPair,
+    HiddenPair,
+    NakedTriple,
+    Fish,
+    HiddenTriple,
+    FinnedFish,
+    XYWing,
+    XYZWing,
+    WWing,
+    UniqueRectangle,
+    NakedQuad,
+    HiddenQuad,
+    Bug,
+    XChain,
+    Aic,
+}
+
+impl Technique {
+    pub const ALL: [Technique; 20] = [
+        Technique::FullHouse,
+        Technique::HiddenSingle,
+        Technique::NakedSingle,
+        Technique::Pointing,
+        Technique::Claiming,
+        Technique::NakedPair,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::Fish,
+        Technique::HiddenTriple,
+        Technique::FinnedFish,
+        Technique::XYWing,
+        Technique::XYZWing,
+        Technique::WWing,
+        Technique::UniqueRectangle,
+        Technique::NakedQuad,
+        Technique::HiddenQuad,
+        Technique::Bug,
+        Technique::XChain,
+        Technique::Aic,
+    ];
+
+    /// Rating of the easiest step the technique can take, from 1.0 for a full house up.
+    pub fn base_rating(&self) -> f64 {
+        match self {
+            Technique::FullHouse => 1.0,
+            Technique::HiddenSingle => 1.5,
+            Technique::NakedSingle => 2.3,
+            Technique::Pointing => 2.6,
+            Technique::Claiming => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::HiddenPair => 3.4,
+            Technique::NakedTriple => 3.6,
+            Technique::Fish => 3.8,
+            Technique::HiddenTriple => 4.0,
+            Technique::FinnedFish => 4.0,
+            Technique::XYWing => 4.2,
+            Technique::XYZWing => 4.4,
+            Technique::WWing => 4.4,
+            Technique::UniqueRectangle => 4.5,
+            Technique::NakedQuad => 5.0,
+            Technique::HiddenQuad => 5.4,
+            Technique::Bug => 5.6,
+            Technique::XChain => 6.5,
+            Technique::Aic => 7.5,
+        }
+    }
+
+    /// Whether the technique is only sound on puzzles with a single solution.
+    pub fn assumes_uniqueness(&self) -> bool {
+        matches!(self, Technique::UniqueRectangle | Technique::Bug)
+    }
+
+    fn find_step<const BOX_SIZE: usize>(
+        &self,
+        grid: &CandidateGrid<BOX_SIZE>,
+        options: &SolverOptions,
+    ) -> Option<Step> {
+        match self {
+            _ if self.assumes_uniqueness() && !options.assume_uniqueness => None,
+            Technique::FullHouse => singles::full_house(grid),
+            Technique::HiddenSingle => singles::hidden_single(grid),
+            Technique::NakedSingle => singles::naked_single(grid),
+            Technique::Pointing => intersections::pointing(grid),
+            Technique::Claiming => intersections::claiming(grid),
+            Technique::NakedPair => subsets::naked_subset(grid, *self, 2),
+            Technique::HiddenPair => subsets::hidden_subset(grid, *self, 2),
+            Technique::NakedTriple => subsets::naked_subset(grid, *self, 3),
+            Technique::HiddenTriple => subsets::hidden_subset(grid, *self, 3),
+            Technique::NakedQuad => subsets::naked_subset(grid, *self, 4),
+            Technique::HiddenQuad => subsets::hidden_subset(grid, *self, 4),
+            Technique::Fish => fish::basic_fish(grid, options.max_fish_size),
+            Technique::FinnedFish => fish::finned_fish(grid, options.max_fish_size),
+            Technique::UniqueRectangle => uniqueness::unique_rectangle(grid),
+            Technique::Bug => uniqueness::bug_plus_one(grid),
+            Technique::XYWing => wings::xy_wing(grid),
+            Technique::XYZWing => wings::xyz_wing(grid),
+            Technique::WWing => wings::w_wing(grid),
+            Technique::XChain => chains::x_chain(grid),
+            Technique::Aic => chains::aic(grid),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum House {
+    Row(usize),
+    Col(usize),
+    Box(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Candidate {
+    pub pos: Vec2D,
+    pub digit: usize,
+}
+
+impl Candidate {
+    pub fn new(pos: Vec2D, digit: usize) -> Self {
+        Self { pos, digit }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepDetail {
+    Cell,
+    House(House),
+    Subset {
+        house: House,
+        digits: CellTile,
+    },
+    Intersection {
+        box_house: House,
+        line: House,
+        digit: usize,
+    },
+    Fish {
+        digit: usize,
+        size: usize,
+        base: Vec<House>,
+        cover: Vec<House>,
+        fins: Vec<Vec2D>,
+    },
+    Wing {
+        pivots: Vec<Vec2D>,
+        pincers: Vec<Vec2D>,
+        digit: usize,
+    },
+    UniqueRectangle {
+        kind: usize,
+        corners: Vec<Vec2D>,
+        digits: CellTile,
+    },
+    Chain(Vec<chains::ChainLink>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub technique: Technique,
+    pub cells: Vec<Vec2D>,
+    pub placements: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+    pub detail: StepDetail,
+}
+
+impl Step {
+    pub fn assumes_uniqueness(&self) -> bool {
+        self.technique.assumes_uniqueness()
+    }
+
+    /// Bigger fish rate higher than the X-Wing their technique's base rating stands for.
+    pub fn rating(&self) -> f64 {
+        match (self.technique, &self.detail) {
+            (Technique::Fish | Technique::FinnedFish, StepDetail::Fish { size, .. }) => {
+                self.technique.base_rating() + 0.6 * (*size as f64 - 2.0)
+            }
+            (Technique::Fish | Technique::FinnedFish, _) => {
+                unreachable!("fish steps always carry their size")
+            }
+            (technique, _) => technique.base_rating(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CandidateGrid<const BOX_SIZE: usize> {
+    pub gameboard: GameBoard<BOX_SIZE>,
+    candidates: Vec<Vec<CellTile>>,
+}
+
+impl<const BOX_SIZE: usize> From<&WaveState<BOX_SIZE>> for CandidateGrid<BOX_SIZE> {
+    fn from(state: &WaveState<BOX_SIZE>) -> Self {
+        let board_size = state.gameboard.board_size();
+        let candidates = (0..board_size)
+            .map(|i| {
+                (0..board_size)
+                    .map(|j| {
+                        state
+                            .entropy_queue
+                            .get_priority(&Vec2D::new(i, j))
+                            .copied()
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            gameboard: state.gameboard.clone(),
+            candidates,
+        }
+    }
+}
+
+impl<const BOX_SIZE: usize> CandidateGrid<BOX_SIZE> {
+    pub fn build(gameboard: GameBoard<BOX_SIZE>) -> Self {
+        Self::from(&WaveState::build(gameboard))
+    }
+
+    pub fn board_size(&self) -> usize {
+        self.gameboard.board_size()
+    }
+
+    pub fn candidates(&self, pos: Vec2D) -> CellTile {
+        self.candidates[pos.x()][pos.y()]
+    }
+
+    pub fn has_candidate(&self, candidate: Candidate) -> bool {
+        self.candidates(candidate.pos).contains(candidate.digit)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.gameboard.is_solved()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Vec2D> {
+        let board_size = self.board_size();
+        (0..board_size).flat_map(move |i| (0..board_size).map(move |j| Vec2D::new(i, j)))
+    }
+
+    pub fn empty_cells(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        self.cells()
+            .filter(|&pos| self.gameboard[pos] == Cell::Empty)
+    }
+
+    pub fn houses(&self) -> impl Iterator<Item = House> {
+        let board_size = self.board_size();
+        (0..board_size)
+            .map(House::Row)
+            .chain((0..board_size).map(House::Col))
+            .chain((0..board_size).map(House::Box))
+    }
+
+    pub fn house_cells(&self, house: House) -> Vec<Vec2D> {
+        let board_size = self.board_size();
+        match house {
+            House::Row(row_idx) => (0..board_size)
+                .map(|col_idx| Vec2D::new(row_idx, col_idx))
+                .collect(),
+            House::Col(col_idx) => (0..board_size)
+                .map(|row_idx| Vec2D::new(row_idx, col_idx))
+                .collect(),
+            House::Box(box_idx) => self
+                .gameboard
+                .box_cell_positions(Vec2D::new(box_idx / BOX_SIZE, box_idx % BOX_SIZE) * BOX_SIZE)
+                .collect(),
+        }
+    }
+
+    pub fn box_index(&self, pos: Vec2D) -> usize {
+        let box_pos = self.gameboard.box_position(pos) / BOX_SIZE;
+        box_pos.x() * BOX_SIZE + box_pos.y()
+    }
+
+    pub fn sees(&self, first: Vec2D, second: Vec2D) -> bool {
+        first != second
+            && (first.x() == second.x()
+                || first.y() == second.y()
+                || self.box_index(first) == self.box_index(second))
+    }
+
+    pub fn peers(&self, pos: Vec2D) -> impl Iterator<Item = Vec2D> + '_ {
+        self.cells().filter(move |&peer| self.sees(pos, peer))
+    }
+
+    pub fn place(&mut self, candidate: Candidate) {
+        let Candidate { pos, digit } = candidate;
+        self.gameboard[pos] = Cell::Guess(digit);
+        self.candidates[pos.x()][pos.y()] = CellTile::default();
+
+        let peers = self.peers(pos).collect::<Vec<_>>();
+        for peer in peers {
+            self.candidates[peer.x()][peer.y()].remove(digit);
+        }
+    }
+
+    pub fn eliminate(&mut self, candidate: Candidate) -> bool {
+        self.candidates[candidate.pos.x()][candidate.pos.y()].remove(candidate.digit)
+    }
+
+    pub fn apply(&mut self, step: &Step) {
+        step.placements
+            .iter()
+            .for_each(|&candidate| self.place(candidate));
+        step.eliminations.iter().for_each(|&candidate| {
+            self.eliminate(candidate);
+        });
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SolverOptions {
+    /// Largest fish to look for; 16x16 and bigger boards can go beyond the Jellyfish.
+    pub max_fish_size: usize,
+    /// Enables deductions that rely on the puzzle having exactly one solution.
+    pub assume_uniqueness: bool,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            max_fish_size: 4,
+            assume_uniqueness: false,
+        }
+    }
+}
+
+pub struct LogicalSolver<const BOX_SIZE: usize> {
+    pub grid: CandidateGrid<BOX_SIZE>,
+    techniques: Vec<Technique>,
+    options: SolverOptions,
+}
+
+impl<const BOX_SIZE: usize> LogicalSolver<BOX_SIZE> {
+    pub fn build(gameboard: GameBoard<BOX_SIZE>) -> Self {
+        Self::with_techniques(gameboard, &Technique::ALL)
+    }
+
+    pub fn with_techniques(gameboard: GameBoard<BOX_SIZE>, techniques: &[Technique]) -> Self {
+        Self::with_options(gameboard, techniques, SolverOptions::default())
+    }
+
+    pub fn with_options(
+        gameboard: GameBoard<BOX_SIZE>,
+        techniques: &[Technique],
+        options: SolverOptions,
+    ) -> Self {
+        let mut techniques = techniques.to_vec();
+        techniques.sort();
+        techniques.dedup();
+
+        Self {
+            grid: CandidateGrid::build(gameboard),
+            techniques,
+            options,
+        }
+    }
+
+    /// The lowest-rated step available. Techniques are tried in rating order, so the search stops
+    /// once no later technique can beat the best step found.
+    pub fn next_step(&self) -> Option<Step> {
+        let mut easiest: Option<Step> = None;
+
+        for technique in self.techniques.iter() {
+            if matches!(&easiest, Some(step) if step.rating() <= technique.base_rating()) {
+                break;
+            }
+            if let Some(step) = technique.find_step(&self.grid, &self.options) {
+                if easiest
+                    .as_ref()
+                    .is_none_or(|best| step.rating() < best.rating())
+                {
+                    easiest = Some(step);
+                }
+            }
+        }
+
+        easiest
+    }
+
+    pub fn step(&mut self) -> Option<Step> {
+        let step = self.next_step()?;
+        self.grid.apply(&step);
+        Some(step)
+    }
+
+    pub fn solve(&mut self) -> Vec<Step> {
+        std::iter::from_fn(|| self.step()).collect()
+    }
+}
+
+pub(super) fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    match (size, items.split_first()) {
+        (0, _) => vec![Vec::new()],
+        (_, None) => Vec::new(),
+        (_, Some((&first, rest))) => {
+            let mut with_first = combinations(rest, size - 1);
+            with_first
+                .iter_mut()
+                .for_each(|combination| combination.insert(0, first));
+            with_first.extend(combinations(rest, size));
+            with_first
+        }
+    }
+}
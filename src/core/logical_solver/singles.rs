@@ -0,0 +1,114 @@
+use super::{Candidate, CandidateGrid, Step, StepDetail, Technique};
+use crate::core::Cell;
+
+pub fn full_house<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    grid.houses().find_map(|house| {
+        let cells = grid.house_cells(house);
+        let mut empty_cells = cells
+            .iter()
+            .filter(|&&pos| grid.gameboard[pos] == Cell::Empty);
+
+        let pos = *empty_cells.next()?;
+        if empty_cells.next().is_some() {
+            return None;
+        }
+
+        let digit = (1..=grid.board_size()).find(|&digit| {
+            cells
+                .iter()
+                .all(|&pos| grid.gameboard[pos].value() != digit)
+        })?;
+
+        Some(Step {
+            technique: Technique::FullHouse,
+            cells: vec![pos],
+            placements: vec![Candidate::new(pos, digit)],
+            eliminations: Vec::new(),
+            detail: StepDetail::House(house),
+        })
+    })
+}
+
+pub fn hidden_single<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    grid.houses().find_map(|house| {
+        let cells = grid.house_cells(house);
+
+        (1..=grid.board_size()).find_map(|digit| {
+            let mut places = cells
+                .iter()
+                .filter(|&&pos| grid.candidates(pos).contains(digit));
+
+            let pos = *places.next()?;
+            if places.next().is_some() {
+                return None;
+            }
+
+            Some(Step {
+                technique: Technique::HiddenSingle,
+                cells: vec![pos],
+                placements: vec![Candidate::new(pos, digit)],
+                eliminations: Vec::new(),
+                detail: StepDetail::House(house),
+            })
+        })
+    })
+}
+
+pub fn naked_single<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    grid.empty_cells().find_map(|pos| {
+        let candidates = grid.candidates(pos);
+        if candidates.len() != 1 {
+            return None;
+        }
+
+        Some(Step {
+            technique: Technique::NakedSingle,
+            cells: vec![pos],
+            placements: vec![Candidate::new(pos, candidates.first()?)],
+            eliminations: Vec::new(),
+            detail: StepDetail::Cell,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::{
+        logical_solver::{LogicalSolver, Technique},
+        test_fixtures, GameBoard,
+    };
+
+    #[test]
+    fn solves_easy_grid_with_singles() {
+        let gameboard = test_fixtures::classic();
+
+        let mut solver = LogicalSolver::build(gameboard);
+        let steps = solver.solve();
+
+        assert!(solver.grid.is_solved());
+        assert_eq!(steps.len(), 49);
+        assert!(steps
+            .iter()
+            .all(|step| step.placements.len() == 1 && Technique::ALL.contains(&step.technique)));
+    }
+
+    #[test]
+    fn naked_singles_alone_stall_where_hidden_singles_are_needed() {
+        let gameboard = GameBoard::<3>::new(vec![
+            vec![0, 0, 0, 0, 0, 0, 0, 1, 2],
+            vec![0, 0, 0, 0, 3, 5, 0, 0, 0],
+            vec![0, 0, 0, 6, 0, 0, 0, 7, 0],
+            vec![7, 0, 0, 0, 0, 0, 3, 0, 0],
+            vec![0, 0, 0, 4, 0, 0, 8, 0, 0],
+            vec![1, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 1, 2, 0, 0, 0, 0],
+            vec![0, 8, 0, 0, 0, 0, 0, 4, 0],
+            vec![0, 5, 0, 0, 0, 0, 6, 0, 0],
+        ]);
+
+        let mut solver = LogicalSolver::with_techniques(gameboard, &[Technique::NakedSingle]);
+        solver.solve();
+
+        assert!(!solver.grid.is_solved());
+    }
+}
@@ -0,0 +1,153 @@
+use super::{combinations, Candidate, CandidateGrid, Step, StepDetail, Technique};
+use crate::core::wave_function_collapse::CellTile;
+
+pub fn naked_subset<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    technique: Technique,
+    size: usize,
+) -> Option<Step> {
+    grid.houses().find_map(|house| {
+        let house_cells = grid.house_cells(house);
+        let subset_cells = house_cells
+            .iter()
+            .copied()
+            .filter(|&pos| (2..=size).contains(&grid.candidates(pos).len()))
+            .collect::<Vec<_>>();
+
+        combinations(&subset_cells, size)
+            .into_iter()
+            .find_map(|cells| {
+                let digits = cells.iter().fold(CellTile::default(), |digits, &pos| {
+                    digits.union(grid.candidates(pos))
+                });
+                if digits.len() != size {
+                    return None;
+                }
+
+                let eliminations = house_cells
+                    .iter()
+                    .filter(|pos| !cells.contains(pos))
+                    .flat_map(|&pos| {
+                        grid.candidates(pos)
+                            .intersection(digits)
+                            .iter()
+                            .map(move |digit| Candidate::new(pos, digit))
+                    })
+                    .collect::<Vec<_>>();
+                if eliminations.is_empty() {
+                    return None;
+                }
+
+                Some(Step {
+                    technique,
+                    cells,
+                    placements: Vec::new(),
+                    eliminations,
+                    detail: StepDetail::Subset { house, digits },
+                })
+            })
+    })
+}
+
+pub fn hidden_subset<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    technique: Technique,
+    size: usize,
+) -> Option<Step> {
+    grid.houses().find_map(|house| {
+        let house_cells = grid.house_cells(house);
+        let digit_places = |digit: usize| {
+            house_cells
+                .iter()
+                .copied()
+                .filter(move |&pos| grid.candidates(pos).contains(digit))
+        };
+
+        let subset_digits = (1..=grid.board_size())
+            .filter(|&digit| (1..=size).contains(&digit_places(digit).count()))
+            .collect::<Vec<_>>();
+
+        combinations(&subset_digits, size)
+            .into_iter()
+            .find_map(|subset| {
+                let digits = subset.iter().copied().collect::<CellTile>();
+                let cells = house_cells
+                    .iter()
+                    .copied()
+                    .filter(|&pos| !grid.candidates(pos).intersection(digits).is_empty())
+                    .collect::<Vec<_>>();
+                if cells.len() != size {
+                    return None;
+                }
+
+                let eliminations = cells
+                    .iter()
+                    .flat_map(|&pos| {
+                        grid.candidates(pos)
+                            .difference(digits)
+                            .iter()
+                            .map(move |digit| Candidate::new(pos, digit))
+                    })
+                    .collect::<Vec<_>>();
+                if eliminations.is_empty() {
+                    return None;
+                }
+
+                Some(Step {
+                    technique,
+                    cells,
+                    placements: Vec::new(),
+                    eliminations,
+                    detail: StepDetail::Subset { house, digits },
+                })
+            })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            logical_solver::{Candidate, CandidateGrid, StepDetail, Technique},
+            GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn finds_naked_pair_in_row() {
+        let mut grid = CandidateGrid::build(GameBoard::<2>::create_empty());
+        for digit in [3, 4] {
+            grid.eliminate(Candidate::new(Vec2D::new(0, 0), digit));
+            grid.eliminate(Candidate::new(Vec2D::new(0, 1), digit));
+        }
+
+        let step = super::naked_subset(&grid, Technique::NakedPair, 2).unwrap();
+
+        assert_eq!(step.cells, vec![Vec2D::new(0, 0), Vec2D::new(0, 1)]);
+        assert!(matches!(step.detail, StepDetail::Subset { digits, .. } if digits.len() == 2));
+        assert!(step
+            .eliminations
+            .iter()
+            .all(|candidate| candidate.pos.x() == 0 && candidate.digit <= 2));
+    }
+
+    #[test]
+    fn finds_hidden_pair_in_row() {
+        let mut grid = CandidateGrid::build(GameBoard::<2>::create_empty());
+        for col_idx in 2..4 {
+            for digit in [1, 2] {
+                grid.eliminate(Candidate::new(Vec2D::new(0, col_idx), digit));
+            }
+        }
+
+        let step = super::hidden_subset(&grid, Technique::HiddenPair, 2).unwrap();
+
+        assert_eq!(step.cells, vec![Vec2D::new(0, 0), Vec2D::new(0, 1)]);
+        assert_eq!(step.eliminations.len(), 4);
+        assert!(step
+            .eliminations
+            .iter()
+            .all(|candidate| candidate.digit >= 3));
+    }
+}
@@ -0,0 +1,313 @@
+use super::{combinations, Candidate, CandidateGrid, House, Step, StepDetail, Technique};
+use crate::{core::wave_function_collapse::CellTile, utils::Vec2D};
+
+pub fn unique_rectangle<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    rectangles(grid).into_iter().find_map(|corners| {
+        let shared = corners
+            .iter()
+            .fold(CellTile::full(grid.board_size()), |shared, &pos| {
+                shared.intersection(grid.candidates(pos))
+            });
+
+        combinations(&shared.iter().collect::<Vec<_>>(), 2)
+            .into_iter()
+            .find_map(|pair| {
+                let digits = pair.into_iter().collect::<CellTile>();
+                let roof = corners
+                    .iter()
+                    .copied()
+                    .filter(|&pos| grid.candidates(pos) != digits)
+                    .collect::<Vec<_>>();
+
+                let (kind, eliminations) = match roof[..] {
+                    [cell] => (1, eliminate_digits(grid, &[cell], digits)),
+                    [first, second] if first.x() == second.x() || first.y() == second.y() => [
+                        (2, type_2(grid, first, second, digits)),
+                        (3, type_3(grid, first, second, digits)),
+                        (4, type_4(grid, first, second, digits)),
+                    ]
+                    .into_iter()
+                    .find(|(_, eliminations)| !eliminations.is_empty())?,
+                    _ => return None,
+                };
+                if eliminations.is_empty() {
+                    return None;
+                }
+
+                Some(Step {
+                    technique: Technique::UniqueRectangle,
+                    cells: corners.to_vec(),
+                    placements: Vec::new(),
+                    eliminations,
+                    detail: StepDetail::UniqueRectangle {
+                        kind,
+                        corners: corners.to_vec(),
+                        digits,
+                    },
+                })
+            })
+    })
+}
+
+pub fn bug_plus_one<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    let mut unsolved = grid
+        .empty_cells()
+        .filter(|&pos| grid.candidates(pos).len() != 2);
+    let pos = unsolved.next()?;
+    if unsolved.next().is_some() || grid.candidates(pos).len() != 3 {
+        return None;
+    }
+
+    // Without the right digit the grid would be a deadly pattern: every digit twice per house.
+    let digit = grid.candidates(pos).iter().find(|&digit| {
+        grid.houses().all(|house| {
+            (1..=grid.board_size()).all(|other| {
+                let count = grid
+                    .house_cells(house)
+                    .into_iter()
+                    .filter(|&cell| grid.candidates(cell).contains(other))
+                    .filter(|&cell| cell != pos || other != digit)
+                    .count();
+                count == 0 || count == 2
+            })
+        })
+    })?;
+
+    Some(Step {
+        technique: Technique::Bug,
+        cells: vec![pos],
+        placements: vec![Candidate::new(pos, digit)],
+        eliminations: Vec::new(),
+        detail: StepDetail::Cell,
+    })
+}
+
+fn rectangles<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Vec<[Vec2D; 4]> {
+    let board_size = grid.board_size();
+    let lines = (0..board_size).collect::<Vec<_>>();
+    let line_pairs = combinations(&lines, 2);
+
+    line_pairs
+        .iter()
+        .flat_map(|rows| {
+            line_pairs.iter().map(move |cols| {
+                [
+                    Vec2D::new(rows[0], cols[0]),
+                    Vec2D::new(rows[0], cols[1]),
+                    Vec2D::new(rows[1], cols[0]),
+                    Vec2D::new(rows[1], cols[1]),
+                ]
+            })
+        })
+        .filter(|corners| corners.iter().all(|&pos| grid.candidates(pos).len() >= 2))
+        .filter(|corners| {
+            let mut boxes = corners
+                .iter()
+                .map(|&pos| grid.box_index(pos))
+                .collect::<Vec<_>>();
+            boxes.sort();
+            boxes.dedup();
+            boxes.len() == 2
+        })
+        .collect()
+}
+
+fn type_2<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    first: Vec2D,
+    second: Vec2D,
+    digits: CellTile,
+) -> Vec<Candidate> {
+    let extra = grid.candidates(first).difference(digits);
+    if extra.len() != 1 || grid.candidates(second).difference(digits) != extra {
+        return Vec::new();
+    }
+
+    let targets = grid
+        .empty_cells()
+        .filter(|&pos| grid.sees(pos, first) && grid.sees(pos, second))
+        .collect::<Vec<_>>();
+    eliminate_digits(grid, &targets, extra)
+}
+
+fn type_3<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    first: Vec2D,
+    second: Vec2D,
+    digits: CellTile,
+) -> Vec<Candidate> {
+    // The two roof cells act as one virtual cell holding only their extra digits.
+    let extras = grid
+        .candidates(first)
+        .union(grid.candidates(second))
+        .difference(digits);
+
+    shared_houses(grid, first, second)
+        .into_iter()
+        .find_map(|house| {
+            let others = grid
+                .house_cells(house)
+                .into_iter()
+                .filter(|&pos| pos != first && pos != second)
+                .filter(|&pos| !grid.candidates(pos).is_empty())
+                .collect::<Vec<_>>();
+
+            (1..others.len().min(4)).find_map(|size| {
+                combinations(&others, size).into_iter().find_map(|subset| {
+                    let subset_digits = subset
+                        .iter()
+                        .fold(extras, |tiles, &pos| tiles.union(grid.candidates(pos)));
+                    if subset_digits.len() != size + 1 {
+                        return None;
+                    }
+
+                    let targets = others
+                        .iter()
+                        .copied()
+                        .filter(|pos| !subset.contains(pos))
+                        .collect::<Vec<_>>();
+                    Some(eliminate_digits(grid, &targets, subset_digits))
+                        .filter(|eliminations| !eliminations.is_empty())
+                })
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn type_4<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    first: Vec2D,
+    second: Vec2D,
+    digits: CellTile,
+) -> Vec<Candidate> {
+    shared_houses(grid, first, second)
+        .into_iter()
+        .find_map(|house| {
+            digits.iter().find_map(|digit| {
+                let conjugate = grid
+                    .house_cells(house)
+                    .into_iter()
+                    .filter(|&pos| grid.candidates(pos).contains(digit))
+                    .all(|pos| pos == first || pos == second);
+                if !conjugate {
+                    return None;
+                }
+
+                let other = digits.difference(CellTile::single(digit));
+                Some(eliminate_digits(grid, &[first, second], other))
+                    .filter(|eliminations| !eliminations.is_empty())
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn shared_houses<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    first: Vec2D,
+    second: Vec2D,
+) -> Vec<House> {
+    let line = match first.x() == second.x() {
+        true => House::Row(first.x()),
+        false => House::Col(first.y()),
+    };
+
+    match grid.box_index(first) == grid.box_index(second) {
+        true => vec![line, House::Box(grid.box_index(first))],
+        false => vec![line],
+    }
+}
+
+fn eliminate_digits<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    targets: &[Vec2D],
+    digits: CellTile,
+) -> Vec<Candidate> {
+    targets
+        .iter()
+        .flat_map(|&pos| digits.iter().map(move |digit| Candidate::new(pos, digit)))
+        .filter(|&candidate| grid.has_candidate(candidate))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            logical_solver::{
+                Candidate, CandidateGrid, LogicalSolver, SolverOptions, StepDetail, Technique,
+            },
+            GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    fn restrict(grid: &mut CandidateGrid<3>, pos: Vec2D, keep: &[usize]) {
+        for digit in (1..=9).filter(|digit| !keep.contains(digit)) {
+            grid.eliminate(Candidate::new(pos, digit));
+        }
+    }
+
+    #[test]
+    fn unique_rectangle_requires_opt_in() {
+        let mut grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        for pos in [Vec2D::new(0, 0), Vec2D::new(0, 3), Vec2D::new(1, 0)] {
+            restrict(&mut grid, pos, &[1, 2]);
+        }
+
+        let mut solver = LogicalSolver::with_techniques(
+            GameBoard::<3>::create_empty(),
+            &[Technique::UniqueRectangle],
+        );
+        solver.grid = grid.clone();
+        assert!(solver.next_step().is_none());
+
+        let mut solver = LogicalSolver::with_options(
+            GameBoard::<3>::create_empty(),
+            &[Technique::UniqueRectangle],
+            SolverOptions {
+                assume_uniqueness: true,
+                ..SolverOptions::default()
+            },
+        );
+        solver.grid = grid;
+        let step = solver.next_step().unwrap();
+
+        assert!(step.assumes_uniqueness());
+        assert!(matches!(
+            step.detail,
+            StepDetail::UniqueRectangle { kind: 1, .. }
+        ));
+        assert_eq!(
+            step.eliminations,
+            vec![
+                Candidate::new(Vec2D::new(1, 3), 1),
+                Candidate::new(Vec2D::new(1, 3), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn unique_rectangle_type_4_uses_conjugate_pair() {
+        let mut grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        for pos in [Vec2D::new(0, 0), Vec2D::new(0, 3)] {
+            restrict(&mut grid, pos, &[1, 2]);
+        }
+        for col in (0..9).filter(|&col| col != 0 && col != 3) {
+            grid.eliminate(Candidate::new(Vec2D::new(1, col), 1));
+        }
+
+        let step = super::unique_rectangle(&grid).unwrap();
+
+        assert!(matches!(
+            step.detail,
+            StepDetail::UniqueRectangle { kind: 4, .. }
+        ));
+        assert_eq!(
+            step.eliminations,
+            vec![
+                Candidate::new(Vec2D::new(1, 0), 2),
+                Candidate::new(Vec2D::new(1, 3), 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use super::{Candidate, CandidateGrid, Step, StepDetail, Technique};
+use crate::{core::wave_function_collapse::CellTile, utils::Vec2D};
+
+pub fn xy_wing<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    let bivalue_cells = cells_with_candidates(grid, 2);
+
+    bivalue_cells.iter().find_map(|&pivot| {
+        let pivot_tiles = grid.candidates(pivot);
+        let pincers = pincers_of(grid, &bivalue_cells, pivot, pivot_tiles);
+
+        pincers.iter().enumerate().find_map(|(idx, &first)| {
+            pincers[idx + 1..].iter().find_map(|&second| {
+                let (first_tiles, second_tiles) = (grid.candidates(first), grid.candidates(second));
+                let shared = first_tiles
+                    .intersection(second_tiles)
+                    .difference(pivot_tiles);
+                if shared.len() != 1
+                    || first_tiles.union(second_tiles).intersection(pivot_tiles) != pivot_tiles
+                {
+                    return None;
+                }
+
+                wing_step(
+                    grid,
+                    Technique::XYWing,
+                    vec![pivot],
+                    vec![first, second],
+                    shared,
+                )
+            })
+        })
+    })
+}
+
+pub fn xyz_wing<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    let bivalue_cells = cells_with_candidates(grid, 2);
+
+    cells_with_candidates(grid, 3)
+        .into_iter()
+        .find_map(|pivot| {
+            let pivot_tiles = grid.candidates(pivot);
+            let pincers = bivalue_cells
+                .iter()
+                .copied()
+                .filter(|&pos| grid.sees(pivot, pos))
+                .filter(|&pos| grid.candidates(pos).difference(pivot_tiles).is_empty())
+                .collect::<Vec<_>>();
+
+            pincers.iter().enumerate().find_map(|(idx, &first)| {
+                pincers[idx + 1..].iter().find_map(|&second| {
+                    let (first_tiles, second_tiles) =
+                        (grid.candidates(first), grid.candidates(second));
+                    let shared = first_tiles.intersection(second_tiles);
+                    if shared.len() != 1 || first_tiles.union(second_tiles) != pivot_tiles {
+                        return None;
+                    }
+
+                    wing_step(
+                        grid,
+                        Technique::XYZWing,
+                        vec![pivot],
+                        vec![first, second],
+                        shared,
+                    )
+                })
+            })
+        })
+}
+
+pub fn w_wing<const BOX_SIZE: usize>(grid: &CandidateGrid<BOX_SIZE>) -> Option<Step> {
+    let bivalue_cells = cells_with_candidates(grid, 2);
+
+    bivalue_cells.iter().enumerate().find_map(|(idx, &first)| {
+        let tiles = grid.candidates(first);
+
+        bivalue_cells[idx + 1..]
+            .iter()
+            .filter(|&&second| grid.candidates(second) == tiles && !grid.sees(first, second))
+            .find_map(|&second| {
+                tiles.iter().find_map(|link_digit| {
+                    let eliminated = tiles.difference(CellTile::single(link_digit));
+
+                    grid.houses().find_map(|house| {
+                        let link_cells = grid
+                            .house_cells(house)
+                            .into_iter()
+                            .filter(|&pos| grid.candidates(pos).contains(link_digit))
+                            .collect::<Vec<_>>();
+                        if link_cells.len() != 2
+                            || link_cells.contains(&first)
+                            || link_cells.contains(&second)
+                        {
+                            return None;
+                        }
+
+                        let (near, far) = (link_cells[0], link_cells[1]);
+                        let connected = (grid.sees(near, first) && grid.sees(far, second))
+                            || (grid.sees(near, second) && grid.sees(far, first));
+                        if !connected {
+                            return None;
+                        }
+
+                        wing_step(
+                            grid,
+                            Technique::WWing,
+                            link_cells,
+                            vec![first, second],
+                            eliminated,
+                        )
+                    })
+                })
+            })
+    })
+}
+
+fn cells_with_candidates<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    count: usize,
+) -> Vec<Vec2D> {
+    grid.empty_cells()
+        .filter(|&pos| grid.candidates(pos).len() == count)
+        .collect()
+}
+
+fn pincers_of<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    bivalue_cells: &[Vec2D],
+    pivot: Vec2D,
+    pivot_tiles: CellTile,
+) -> Vec<Vec2D> {
+    bivalue_cells
+        .iter()
+        .copied()
+        .filter(|&pos| grid.sees(pivot, pos))
+        .filter(|&pos| grid.candidates(pos).intersection(pivot_tiles).len() == 1)
+        .collect()
+}
+
+fn wing_step<const BOX_SIZE: usize>(
+    grid: &CandidateGrid<BOX_SIZE>,
+    technique: Technique,
+    pivots: Vec<Vec2D>,
+    pincers: Vec<Vec2D>,
+    eliminated: CellTile,
+) -> Option<Step> {
+    let digit = eliminated.first()?;
+    let must_see = match technique {
+        Technique::XYZWing => pivots.iter().chain(pincers.iter()).copied().collect(),
+        _ => pincers.clone(),
+    };
+
+    let eliminations = grid
+        .empty_cells()
+        .filter(|&pos| must_see.iter().all(|&wing_cell| grid.sees(pos, wing_cell)))
+        .map(|pos| Candidate::new(pos, digit))
+        .filter(|&candidate| grid.has_candidate(candidate))
+        .collect::<Vec<_>>();
+    if eliminations.is_empty() {
+        return None;
+    }
+
+    Some(Step {
+        technique,
+        cells: pivots.iter().chain(pincers.iter()).copied().collect(),
+        placements: Vec::new(),
+        eliminations,
+        detail: StepDetail::Wing {
+            pivots,
+            pincers,
+            digit,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            logical_solver::{Candidate, CandidateGrid, Technique},
+            GameBoard,
+        },
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn finds_xy_wing() {
+        let mut grid = CandidateGrid::build(GameBoard::<3>::create_empty());
+        let restrict = |grid: &mut CandidateGrid<3>, pos: Vec2D, keep: [usize; 2]| {
+            for digit in (1..=9).filter(|digit| !keep.contains(digit)) {
+                grid.eliminate(Candidate::new(pos, digit));
+            }
+        };
+        restrict(&mut grid, Vec2D::new(0, 0), [1, 2]);
+        restrict(&mut grid, Vec2D::new(0, 5), [1, 3]);
+        restrict(&mut grid, Vec2D::new(5, 0), [2, 3]);
+
+        let step = super::xy_wing(&grid).unwrap();
+
+        assert_eq!(step.technique, Technique::XYWing);
+        assert_eq!(step.eliminations, vec![Candidate::new(Vec2D::new(5, 5), 3)]);
+    }
+}
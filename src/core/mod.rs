@@ -1,8 +1,21 @@
 use crate::utils::Vec2D;
 
+pub mod backtracking_solver;
+pub mod dancing_links;
+pub mod difficulty;
+pub mod exploratory_decomposition;
+pub mod genetic_search;
+pub mod hint;
+pub mod logical_solver;
+pub mod sat;
+pub mod solution_iterator;
+pub mod solver;
+pub mod stochastic_search;
+#[cfg(test)]
+mod test_fixtures;
 pub mod wave_function_collapse;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Cell {
     Empty,
     Given(usize),
@@ -50,10 +63,50 @@ impl<const BOX_SIZE: usize> GameBoard<BOX_SIZE> {
         Self(vec![vec![Cell::Empty; Self::BOARD_SIZE]; Self::BOARD_SIZE])
     }
 
+    pub fn new(board: Vec<Vec<usize>>) -> Self {
+        Self(
+            board
+                .into_iter()
+                .map(|row| row.into_iter().map(Cell::as_given).collect())
+                .collect(),
+        )
+    }
+
+    pub fn as_raw(&self) -> Vec<Vec<usize>> {
+        self.0
+            .iter()
+            .map(|row| row.iter().map(Cell::value).collect())
+            .collect()
+    }
+
     pub fn board_size(&self) -> usize {
         Self::BOARD_SIZE
     }
 
+    pub fn is_solved(&self) -> bool {
+        self.0.iter().flatten().all(|cell| *cell != Cell::Empty) && self.is_consistent()
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        (0..Self::BOARD_SIZE).all(|idx| {
+            let row = (0..Self::BOARD_SIZE).map(|col_idx| Vec2D::new(idx, col_idx));
+            let col = (0..Self::BOARD_SIZE).map(|row_idx| Vec2D::new(row_idx, idx));
+            let box_pos = Vec2D::new(idx / BOX_SIZE, idx % BOX_SIZE) * BOX_SIZE;
+
+            self.is_unit_consistent(row)
+                && self.is_unit_consistent(col)
+                && self.is_unit_consistent(self.box_cell_positions(box_pos))
+        })
+    }
+
+    fn is_unit_consistent(&self, cells: impl Iterator<Item = Vec2D>) -> bool {
+        let mut seen = vec![false; Self::BOARD_SIZE + 1];
+        cells
+            .map(|pos| self[pos].value())
+            .filter(|&val| val != 0)
+            .all(|val| !std::mem::replace(&mut seen[val], true))
+    }
+
     fn box_position(&self, cell_pos: Vec2D) -> Vec2D {
         (cell_pos / BOX_SIZE) * BOX_SIZE
     }
@@ -86,14 +139,14 @@ impl<const BOX_SIZE: usize> std::fmt::Debug for GameBoard<BOX_SIZE> {
         write!(f, "\n")?;
 
         for (row_idx, row) in self.0.iter().enumerate() {
-            if row_idx != 0 && row_idx % 3 == 0 {
-                for _ in 0..11 {
+            if row_idx != 0 && row_idx % BOX_SIZE == 0 {
+                for _ in 0..BOX_SIZE * (BOX_SIZE + 1) - 1 {
                     write!(f, "\x1b[90m-\x1b[0m ")?;
                 }
                 write!(f, "\n")?;
             }
             for (col_idx, cell) in row.iter().enumerate() {
-                if col_idx != 0 && col_idx % 3 == 0 {
+                if col_idx != 0 && col_idx % BOX_SIZE == 0 {
                     write!(f, "\x1b[90m|\x1b[0m ")?;
                 }
                 write!(f, "{:?} ", cell)?;
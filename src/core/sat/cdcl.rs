@@ -0,0 +1,435 @@
+use super::cnf::Cnf;
+
+const ACTIVITY_DECAY: f64 = 0.95;
+const ACTIVITY_LIMIT: f64 = 1e100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SatResult {
+    /// Value of every variable, as DIMACS literals ordered by variable.
+    Satisfiable(Vec<i32>),
+    Unsatisfiable,
+}
+
+// Literals are stored as `2 * var + is_negated` so they index straight into `watches`.
+type Lit = usize;
+
+fn to_lit(literal: i32) -> Lit {
+    (literal.unsigned_abs() as usize - 1) * 2 + (literal < 0) as usize
+}
+
+pub struct Cdcl {
+    clauses: Vec<Vec<Lit>>,
+    // Clauses watching each literal; the watched literals are the first two of a clause.
+    watches: Vec<Vec<usize>>,
+    assignment: Vec<Option<bool>>,
+    level: Vec<usize>,
+    reason: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    trail_limits: Vec<usize>,
+    propagated: usize,
+    activity: Vec<f64>,
+    activity_increment: f64,
+    order: VarOrder,
+    saved_phase: Vec<bool>,
+    seen: Vec<bool>,
+    inconsistent: bool,
+    decisions: u64,
+    conflicts: u64,
+    propagations: u64,
+    max_decision_level: usize,
+}
+
+impl Cdcl {
+    pub fn build(cnf: &Cnf) -> Self {
+        let mut cdcl = Self {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); cnf.num_vars * 2],
+            assignment: vec![None; cnf.num_vars],
+            level: vec![0; cnf.num_vars],
+            reason: vec![None; cnf.num_vars],
+            trail: Vec::new(),
+            trail_limits: Vec::new(),
+            propagated: 0,
+            activity: vec![0.0; cnf.num_vars],
+            activity_increment: 1.0,
+            order: VarOrder::new(cnf.num_vars),
+            saved_phase: vec![false; cnf.num_vars],
+            seen: vec![false; cnf.num_vars],
+            inconsistent: false,
+            decisions: 0,
+            conflicts: 0,
+            propagations: 0,
+            max_decision_level: 0,
+        };
+
+        for clause in cnf.clauses.iter() {
+            let mut lits = clause
+                .iter()
+                .map(|&literal| to_lit(literal))
+                .collect::<Vec<_>>();
+            lits.sort();
+            lits.dedup();
+            if lits.windows(2).any(|pair| pair[0] ^ 1 == pair[1]) {
+                continue;
+            }
+
+            match lits.len() {
+                0 => cdcl.inconsistent = true,
+                1 => match cdcl.lit_value(lits[0]) {
+                    Some(false) => cdcl.inconsistent = true,
+                    Some(true) => {}
+                    None => cdcl.enqueue(lits[0], None),
+                },
+                _ => {
+                    cdcl.add_clause(lits);
+                }
+            }
+        }
+
+        cdcl
+    }
+
+    pub fn decisions(&self) -> u64 {
+        self.decisions
+    }
+
+    pub fn conflicts(&self) -> u64 {
+        self.conflicts
+    }
+
+    pub fn propagations(&self) -> u64 {
+        self.propagations
+    }
+
+    pub fn max_decision_level(&self) -> usize {
+        self.max_decision_level
+    }
+
+    pub fn solve(&mut self) -> SatResult {
+        self.solve_until(|| true).unwrap()
+    }
+
+    /// Asks `keep_going` before every decision and returns `None` as soon as it says no.
+    pub fn solve_until(&mut self, mut keep_going: impl FnMut() -> bool) -> Option<SatResult> {
+        if self.inconsistent {
+            return Some(SatResult::Unsatisfiable);
+        }
+
+        loop {
+            match self.propagate() {
+                Some(_) if self.trail_limits.is_empty() => {
+                    self.inconsistent = true;
+                    return Some(SatResult::Unsatisfiable);
+                }
+                Some(conflict) => {
+                    self.conflicts += 1;
+                    let (learnt, backjump_level) = self.analyze(conflict);
+                    self.backtrack(backjump_level);
+                    self.learn(learnt);
+                    self.activity_increment /= ACTIVITY_DECAY;
+                }
+                None => match self.pick_branch_lit() {
+                    None => return Some(SatResult::Satisfiable(self.model())),
+                    Some(_) if !keep_going() => return None,
+                    Some(lit) => {
+                        self.decisions += 1;
+                        self.trail_limits.push(self.trail.len());
+                        self.max_decision_level =
+                            self.max_decision_level.max(self.decision_level());
+                        self.enqueue(lit, None);
+                    }
+                },
+            }
+        }
+    }
+
+    fn lit_value(&self, lit: Lit) -> Option<bool> {
+        self.assignment[lit >> 1].map(|value| value != (lit & 1 == 1))
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_limits.len()
+    }
+
+    fn add_clause(&mut self, lits: Vec<Lit>) -> usize {
+        let clause_idx = self.clauses.len();
+        self.watches[lits[0]].push(clause_idx);
+        self.watches[lits[1]].push(clause_idx);
+        self.clauses.push(lits);
+        clause_idx
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit >> 1;
+        self.assignment[var] = Some(lit & 1 == 0);
+        self.level[var] = self.decision_level();
+        self.reason[var] = reason;
+        self.trail.push(lit);
+    }
+
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let false_lit = self.trail[self.propagated] ^ 1;
+            self.propagated += 1;
+            self.propagations += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_lit]);
+            let mut idx = 0;
+            while idx < watchers.len() {
+                let clause_idx = watchers[idx];
+                let clause = &mut self.clauses[clause_idx];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+
+                let first = clause[0];
+                let value_of =
+                    |lit: Lit| self.assignment[lit >> 1].map(|value| value != (lit & 1 == 1));
+                if value_of(first) == Some(true) {
+                    idx += 1;
+                    continue;
+                }
+
+                match (2..clause.len()).find(|&other| value_of(clause[other]) != Some(false)) {
+                    Some(other) => {
+                        clause.swap(1, other);
+                        self.watches[clause[1]].push(clause_idx);
+                        watchers.swap_remove(idx);
+                    }
+                    None if value_of(first) == Some(false) => {
+                        self.watches[false_lit] = watchers;
+                        self.propagated = self.trail.len();
+                        return Some(clause_idx);
+                    }
+                    None => {
+                        self.enqueue(first, Some(clause_idx));
+                        idx += 1;
+                    }
+                }
+            }
+            self.watches[false_lit] = watchers;
+        }
+
+        None
+    }
+
+    // First unique implication point: resolve conflict reasons until one literal of the current
+    // level remains; it becomes the asserting literal at the front of the learnt clause.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut learnt = vec![0];
+        let mut pending = 0;
+        let mut implied = None;
+        let mut clause_idx = conflict;
+        let mut trail_idx = self.trail.len();
+
+        loop {
+            for lit_idx in 0..self.clauses[clause_idx].len() {
+                let lit = self.clauses[clause_idx][lit_idx];
+                let var = lit >> 1;
+                if Some(lit) == implied || self.seen[var] || self.level[var] == 0 {
+                    continue;
+                }
+
+                self.seen[var] = true;
+                self.bump_activity(var);
+                match self.level[var] == self.decision_level() {
+                    true => pending += 1,
+                    false => learnt.push(lit),
+                }
+            }
+
+            let lit = loop {
+                trail_idx -= 1;
+                if self.seen[self.trail[trail_idx] >> 1] {
+                    break self.trail[trail_idx];
+                }
+            };
+            self.seen[lit >> 1] = false;
+            pending -= 1;
+            if pending == 0 {
+                learnt[0] = lit ^ 1;
+                break;
+            }
+            clause_idx = self.reason[lit >> 1].unwrap();
+            implied = Some(lit);
+        }
+
+        learnt[1..]
+            .iter()
+            .for_each(|&lit| self.seen[lit >> 1] = false);
+
+        let backjump_level = match (1..learnt.len()).max_by_key(|&idx| self.level[learnt[idx] >> 1])
+        {
+            None => 0,
+            Some(idx) => {
+                learnt.swap(1, idx);
+                self.level[learnt[1] >> 1]
+            }
+        };
+
+        (learnt, backjump_level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+
+        for &lit in self.trail[self.trail_limits[level]..].iter() {
+            let var = lit >> 1;
+            self.saved_phase[var] = lit & 1 == 0;
+            self.assignment[var] = None;
+            self.reason[var] = None;
+            self.order.insert(var, &self.activity);
+        }
+        self.trail.truncate(self.trail_limits[level]);
+        self.trail_limits.truncate(level);
+        self.propagated = self.trail.len();
+    }
+
+    fn learn(&mut self, learnt: Vec<Lit>) {
+        let asserting = learnt[0];
+        match learnt.len() {
+            1 => self.enqueue(asserting, None),
+            _ => {
+                let clause_idx = self.add_clause(learnt);
+                self.enqueue(asserting, Some(clause_idx));
+            }
+        }
+    }
+
+    fn bump_activity(&mut self, var: usize) {
+        self.activity[var] += self.activity_increment;
+        if self.activity[var] > ACTIVITY_LIMIT {
+            self.activity
+                .iter_mut()
+                .for_each(|activity| *activity /= ACTIVITY_LIMIT);
+            self.activity_increment /= ACTIVITY_LIMIT;
+        }
+        self.order.increased(var, &self.activity);
+    }
+
+    // Assigned variables stay in the order until they surface here and are skipped.
+    fn pick_branch_lit(&mut self) -> Option<Lit> {
+        let var = std::iter::from_fn(|| self.order.pop_max(&self.activity))
+            .find(|&var| self.assignment[var].is_none())?;
+
+        Some(var * 2 + !self.saved_phase[var] as usize)
+    }
+
+    fn model(&self) -> Vec<i32> {
+        self.assignment
+            .iter()
+            .enumerate()
+            .map(|(var, value)| match value {
+                Some(true) => var as i32 + 1,
+                _ => -(var as i32 + 1),
+            })
+            .collect()
+    }
+}
+
+/// Binary max-heap of variables by activity, with each variable's heap position so a bumped
+/// variable can be sifted up in place instead of scanning every variable per decision.
+struct VarOrder {
+    heap: Vec<usize>,
+    position: Vec<Option<usize>>,
+}
+
+impl VarOrder {
+    // Every activity starts at zero, so any order is a valid heap.
+    fn new(num_vars: usize) -> Self {
+        Self {
+            heap: (0..num_vars).collect(),
+            position: (0..num_vars).map(Some).collect(),
+        }
+    }
+
+    fn insert(&mut self, var: usize, activity: &[f64]) {
+        if self.position[var].is_some() {
+            return;
+        }
+        self.position[var] = Some(self.heap.len());
+        self.heap.push(var);
+        self.sift_up(self.heap.len() - 1, activity);
+    }
+
+    fn increased(&mut self, var: usize, activity: &[f64]) {
+        if let Some(idx) = self.position[var] {
+            self.sift_up(idx, activity);
+        }
+    }
+
+    fn pop_max(&mut self, activity: &[f64]) -> Option<usize> {
+        let last = self.heap.len().checked_sub(1)?;
+        self.swap(0, last);
+        let var = self.heap.pop()?;
+        self.position[var] = None;
+        self.sift_down(0, activity);
+        Some(var)
+    }
+
+    fn sift_up(&mut self, mut idx: usize, activity: &[f64]) {
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            if activity[self.heap[idx]] <= activity[self.heap[parent]] {
+                break;
+            }
+            self.swap(idx, parent);
+            idx = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut idx: usize, activity: &[f64]) {
+        loop {
+            let largest = [2 * idx + 1, 2 * idx + 2]
+                .into_iter()
+                .filter(|&child| child < self.heap.len())
+                .fold(idx, |largest, child| {
+                    match activity[self.heap[child]] > activity[self.heap[largest]] {
+                        true => child,
+                        false => largest,
+                    }
+                });
+            if largest == idx {
+                break;
+            }
+            self.swap(idx, largest);
+            idx = largest;
+        }
+    }
+
+    fn swap(&mut self, first: usize, second: usize) {
+        self.heap.swap(first, second);
+        self.position[self.heap[first]] = Some(first);
+        self.position[self.heap[second]] = Some(second);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::VarOrder;
+
+    #[test]
+    fn var_order_pops_by_activity() {
+        let mut activity = vec![0.0; 6];
+        let mut order = VarOrder::new(activity.len());
+
+        for (var, value) in [(3, 2.0), (1, 5.0), (4, 1.0)] {
+            activity[var] = value;
+            order.increased(var, &activity);
+        }
+        assert_eq!(order.pop_max(&activity), Some(1));
+        assert_eq!(order.pop_max(&activity), Some(3));
+
+        activity[1] = 9.0;
+        order.insert(1, &activity);
+        order.insert(1, &activity);
+        assert_eq!(order.pop_max(&activity), Some(1));
+        assert_eq!(order.pop_max(&activity), Some(4));
+
+        let rest = std::iter::from_fn(|| order.pop_max(&activity)).count();
+        assert_eq!(rest, 3);
+        assert_eq!(order.pop_max(&activity), None);
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DimacsError {
+    MissingHeader,
+    InvalidHeader(String),
+    InvalidLiteral(String),
+    VariableOutOfRange(i32),
+    /// The model leaves a cell empty, fills it twice or contradicts the digit already there.
+    MismatchedCell(usize, usize),
+    UnterminatedClause,
+    Unsatisfiable,
+}
+
+impl fmt::Display for DimacsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DimacsError::MissingHeader => write!(f, "missing `p cnf` header"),
+            DimacsError::InvalidHeader(line) => write!(f, "invalid header `{}`", line),
+            DimacsError::InvalidLiteral(token) => write!(f, "invalid literal `{}`", token),
+            DimacsError::VariableOutOfRange(literal) => {
+                write!(f, "literal {} is out of the declared range", literal)
+            }
+            DimacsError::MismatchedCell(row, col) => {
+                write!(
+                    f,
+                    "model does not fit the cell at row {} column {}",
+                    row, col
+                )
+            }
+            DimacsError::UnterminatedClause => write!(f, "last clause is missing its 0"),
+            DimacsError::Unsatisfiable => write!(f, "solver reported the formula unsatisfiable"),
+        }
+    }
+}
+
+impl std::error::Error for DimacsError {}
+
+/// Formula in conjunctive normal form, with literals numbered as in DIMACS: variable `v` is
+/// `v` when true and `-v` when false.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cnf {
+    pub num_vars: usize,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+impl Cnf {
+    pub fn new(num_vars: usize) -> Self {
+        Self {
+            num_vars,
+            clauses: Vec::new(),
+        }
+    }
+
+    pub fn add_clause(&mut self, clause: Vec<i32>) {
+        self.clauses.push(clause);
+    }
+
+    pub fn at_most_one(&mut self, literals: &[i32]) {
+        for (idx, &first) in literals.iter().enumerate() {
+            for &second in literals[idx + 1..].iter() {
+                self.add_clause(vec![-first, -second]);
+            }
+        }
+    }
+
+    pub fn exactly_one(&mut self, literals: &[i32]) {
+        self.add_clause(literals.to_vec());
+        self.at_most_one(literals);
+    }
+
+    pub fn to_dimacs(&self) -> String {
+        let mut dimacs = format!("p cnf {} {}\n", self.num_vars, self.clauses.len());
+        for clause in self.clauses.iter() {
+            for literal in clause.iter() {
+                dimacs.push_str(&literal.to_string());
+                dimacs.push(' ');
+            }
+            dimacs.push_str("0\n");
+        }
+        dimacs
+    }
+
+    pub fn from_dimacs(dimacs: &str) -> Result<Self, DimacsError> {
+        let mut lines = dimacs
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('c'));
+
+        let header = lines.next().ok_or(DimacsError::MissingHeader)?;
+        let num_vars = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            // Literals are `i32`, so more variables could never be referred to.
+            ["p", "cnf", num_vars, _] => match num_vars.parse::<usize>() {
+                Ok(num_vars) if num_vars <= i32::MAX as usize => num_vars,
+                _ => return Err(DimacsError::InvalidHeader(header.to_string())),
+            },
+            ["p", ..] => return Err(DimacsError::InvalidHeader(header.to_string())),
+            _ => return Err(DimacsError::MissingHeader),
+        };
+
+        let mut cnf = Cnf::new(num_vars);
+        let mut clause = Vec::new();
+        for token in lines.flat_map(str::split_whitespace) {
+            match token.parse::<i32>() {
+                Ok(0) => cnf.add_clause(std::mem::take(&mut clause)),
+                Ok(literal) if literal.unsigned_abs() as usize > num_vars => {
+                    return Err(DimacsError::VariableOutOfRange(literal))
+                }
+                Ok(literal) => clause.push(literal),
+                Err(_) => return Err(DimacsError::InvalidLiteral(token.to_string())),
+            }
+        }
+
+        match clause.is_empty() {
+            true => Ok(cnf),
+            false => Err(DimacsError::UnterminatedClause),
+        }
+    }
+}
+
+/// Reads the `v` lines of a SAT competition style solver output into model literals.
+pub fn parse_dimacs_model(output: &str) -> Result<Vec<i32>, DimacsError> {
+    let mut model = Vec::new();
+
+    for line in output.lines().map(str::trim) {
+        match line.split_whitespace().next() {
+            Some("s") if line.contains("UNSATISFIABLE") => return Err(DimacsError::Unsatisfiable),
+            Some("v") => {
+                for token in line.split_whitespace().skip(1) {
+                    match token.parse::<i32>() {
+                        Ok(0) => {}
+                        Ok(literal) => model.push(literal),
+                        Err(_) => return Err(DimacsError::InvalidLiteral(token.to_string())),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(model)
+}
@@ -0,0 +1,239 @@
+use super::{Cell, GameBoard};
+use crate::utils::Vec2D;
+
+pub mod cdcl;
+pub mod cnf;
+
+use cdcl::{Cdcl, SatResult};
+use cnf::{parse_dimacs_model, Cnf, DimacsError};
+
+impl<const BOX_SIZE: usize> GameBoard<BOX_SIZE> {
+    /// DIMACS variable that is true when `digit` sits at `pos`.
+    pub fn sat_variable(pos: Vec2D, digit: usize) -> i32 {
+        (pos.project(Self::BOARD_SIZE) * Self::BOARD_SIZE + digit) as i32
+    }
+
+    pub fn to_cnf(&self) -> Cnf {
+        let board_size = self.board_size();
+        let mut cnf = Cnf::new(board_size * board_size * board_size);
+        let cells = (0..board_size)
+            .flat_map(|i| (0..board_size).map(move |j| Vec2D::new(i, j)))
+            .collect::<Vec<_>>();
+
+        for &pos in cells.iter() {
+            let literals = (1..=board_size)
+                .map(|digit| Self::sat_variable(pos, digit))
+                .collect::<Vec<_>>();
+            cnf.exactly_one(&literals);
+        }
+
+        let houses = (0..board_size).flat_map(|idx| {
+            let box_pos = Vec2D::new(idx / BOX_SIZE, idx % BOX_SIZE) * BOX_SIZE;
+            [
+                (0..board_size).map(|j| Vec2D::new(idx, j)).collect(),
+                (0..board_size).map(|i| Vec2D::new(i, idx)).collect(),
+                self.box_cell_positions(box_pos).collect::<Vec<_>>(),
+            ]
+        });
+        for house in houses {
+            for digit in 1..=board_size {
+                let literals = house
+                    .iter()
+                    .map(|&pos| Self::sat_variable(pos, digit))
+                    .collect::<Vec<_>>();
+                cnf.exactly_one(&literals);
+            }
+        }
+
+        for &pos in cells.iter() {
+            match self[pos] {
+                // Guesses are held like givens, so a wrong one makes the formula unsatisfiable.
+                Cell::Given(digit) | Cell::Guess(digit) => {
+                    cnf.add_clause(vec![Self::sat_variable(pos, digit)])
+                }
+                Cell::Empty => {}
+            }
+        }
+
+        cnf
+    }
+
+    pub fn to_dimacs(&self) -> String {
+        self.to_cnf().to_dimacs()
+    }
+
+    /// Fills the empty cells from the true variables of a model, leaving filled cells alone. The
+    /// model must come from this board's formula; outside ones go through `read_dimacs_model`.
+    pub(in crate::core) fn with_model(&self, model: &[i32]) -> Self {
+        let mut gameboard = self.clone();
+        let board_size = self.board_size() as i32;
+
+        for &literal in model.iter().filter(|&&literal| literal > 0) {
+            let idx = literal - 1;
+            let pos = Vec2D::new(
+                (idx / (board_size * board_size)) as usize,
+                (idx / board_size % board_size) as usize,
+            );
+            if gameboard[pos] == Cell::Empty {
+                gameboard[pos] = Cell::Guess((idx % board_size) as usize + 1);
+            }
+        }
+
+        gameboard
+    }
+
+    /// Reads a solver's model, checking that it places exactly one digit in every cell and keeps
+    /// the digits already on the board.
+    pub fn read_dimacs_model(&self, output: &str) -> Result<Self, DimacsError> {
+        let model = parse_dimacs_model(output)?;
+        let board_size = self.board_size();
+        if let Some(&literal) = model
+            .iter()
+            .find(|literal| literal.unsigned_abs() as usize > board_size.pow(3))
+        {
+            return Err(DimacsError::VariableOutOfRange(literal));
+        }
+
+        let mut cell_digits = vec![Vec::new(); board_size * board_size];
+        for &literal in model.iter().filter(|&&literal| literal > 0) {
+            let idx = literal as usize - 1;
+            let digits = &mut cell_digits[idx / board_size];
+            if !digits.contains(&(idx % board_size + 1)) {
+                digits.push(idx % board_size + 1);
+            }
+        }
+        for (cell_idx, digits) in cell_digits.iter().enumerate() {
+            let pos = Vec2D::new(cell_idx / board_size, cell_idx % board_size);
+            let fits = match (self[pos], &digits[..]) {
+                (Cell::Empty, [_]) => true,
+                (Cell::Given(digit) | Cell::Guess(digit), [model_digit]) => digit == *model_digit,
+                _ => false,
+            };
+            if !fits {
+                return Err(DimacsError::MismatchedCell(pos.x(), pos.y()));
+            }
+        }
+
+        Ok(self.with_model(&model))
+    }
+
+    pub fn sat_solver(&self) -> Option<Self> {
+        match Cdcl::build(&self.to_cnf()).solve() {
+            SatResult::Satisfiable(model) => Some(self.with_model(&model)),
+            SatResult::Unsatisfiable => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::cnf::{Cnf, DimacsError};
+    use crate::{
+        core::{test_fixtures, Cell, GameBoard},
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn cdcl_solves_encoded_grid() {
+        let gameboard = test_fixtures::hard();
+
+        let solution = gameboard.sat_solver().unwrap();
+
+        assert!(solution.is_solved());
+        assert_eq!(solution[Vec2D::new(0, 0)], Cell::Given(8));
+        assert_eq!(solution.as_raw()[0], vec![8, 1, 2, 7, 5, 3, 6, 4, 9]);
+    }
+
+    #[test]
+    fn cdcl_proves_conflicting_givens_unsatisfiable() {
+        let mut gameboard = GameBoard::<2>::create_empty();
+        gameboard[Vec2D::new(0, 0)] = Cell::Given(1);
+        gameboard[Vec2D::new(1, 1)] = Cell::Given(2);
+        gameboard[Vec2D::new(2, 2)] = Cell::Given(1);
+        gameboard[Vec2D::new(3, 3)] = Cell::Given(1);
+
+        assert!(gameboard.sat_solver().is_none());
+    }
+
+    #[test]
+    fn cdcl_rejects_wrong_guess() {
+        let mut gameboard = test_fixtures::classic();
+        gameboard[Vec2D::new(0, 1)] = Cell::Guess(9);
+
+        assert!(gameboard.is_consistent());
+        assert!(gameboard.sat_solver().is_none());
+    }
+
+    #[test]
+    fn dimacs_round_trip() {
+        let mut gameboard = GameBoard::<2>::create_empty();
+        gameboard[Vec2D::new(0, 0)] = Cell::Given(3);
+        let cnf = gameboard.to_cnf();
+
+        assert_eq!(Cnf::from_dimacs(&cnf.to_dimacs()), Ok(cnf));
+        assert_eq!(
+            Cnf::from_dimacs("p cnf 2 1\n1 -3 0\n"),
+            Err(DimacsError::VariableOutOfRange(-3))
+        );
+        assert_eq!(
+            Cnf::from_dimacs("p cnf 99999999999 1\n1 0\n"),
+            Err(DimacsError::InvalidHeader(
+                "p cnf 99999999999 1".to_string()
+            ))
+        );
+
+        let solution = gameboard.sat_solver().unwrap();
+        let model_output = |fills: &dyn Fn(Vec2D, usize) -> bool| {
+            let literals = (0..4)
+                .flat_map(|i| (0..4).map(move |j| Vec2D::new(i, j)))
+                .flat_map(|pos| {
+                    (1..=4).map(move |digit| match fills(pos, digit) {
+                        true => GameBoard::<2>::sat_variable(pos, digit),
+                        false => -GameBoard::<2>::sat_variable(pos, digit),
+                    })
+                })
+                .map(|literal| literal.to_string())
+                .collect::<Vec<_>>();
+            format!("s SATISFIABLE\nv {} 0\n", literals.join(" "))
+        };
+
+        let read_back = gameboard
+            .read_dimacs_model(&model_output(&|pos, digit| solution[pos].value() == digit))
+            .unwrap();
+        assert_eq!(read_back.as_raw(), solution.as_raw());
+        assert_eq!(read_back[Vec2D::new(0, 0)], Cell::Given(3));
+
+        let incomplete =
+            model_output(&|pos, digit| pos != Vec2D::new(2, 1) && solution[pos].value() == digit);
+        let doubled = model_output(&|pos, digit| {
+            solution[pos].value() == digit || (pos == Vec2D::new(1, 2) && digit == 1)
+        });
+        let contradicting = model_output(&|pos, digit| match pos == Vec2D::new(0, 0) {
+            true => digit == 4,
+            false => solution[pos].value() == digit,
+        });
+        for (output, pos) in [
+            (incomplete, Vec2D::new(2, 1)),
+            (doubled, Vec2D::new(1, 2)),
+            (contradicting, Vec2D::new(0, 0)),
+        ] {
+            assert_eq!(
+                gameboard.read_dimacs_model(&output).err(),
+                Some(DimacsError::MismatchedCell(pos.x(), pos.y()))
+            );
+        }
+
+        assert_eq!(
+            gameboard
+                .read_dimacs_model("s SATISFIABLE\nv 5000 0\n")
+                .err(),
+            Some(DimacsError::VariableOutOfRange(5000))
+        );
+        assert_eq!(
+            gameboard
+                .read_dimacs_model("s SATISFIABLE\nv -65 0\n")
+                .err(),
+            Some(DimacsError::VariableOutOfRange(-65))
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use super::{
+    wave_function_collapse::{Decomposition, WaveFunction, WaveState},
+    GameBoard,
+};
+
+pub struct WaveSolutions<const BOX_SIZE: usize> {
+    pending_states: Vec<WaveState<BOX_SIZE>>,
+}
+
+impl<const BOX_SIZE: usize> Iterator for WaveSolutions<BOX_SIZE> {
+    type Item = GameBoard<BOX_SIZE>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(mut state) = self.pending_states.pop() {
+            match state.decompose() {
+                Decomposition::Solved => return Some(state.gameboard),
+                Decomposition::DeadEnd => {}
+                Decomposition::Branched(branches) => {
+                    self.pending_states.extend(branches.into_iter().rev())
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<const BOX_SIZE: usize> WaveFunction<BOX_SIZE> {
+    pub fn solutions(gameboard: GameBoard<BOX_SIZE>) -> WaveSolutions<BOX_SIZE> {
+        let pending_states = match gameboard.is_consistent() {
+            true => vec![WaveState::build(gameboard)],
+            false => Vec::new(),
+        };
+
+        WaveSolutions { pending_states }
+    }
+
+    pub fn count_solutions(gameboard: GameBoard<BOX_SIZE>, limit: usize) -> usize {
+        Self::solutions(gameboard).take(limit).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::{test_fixtures, wave_function_collapse::WaveFunction, GameBoard};
+
+    #[test]
+    fn counts_every_4x4_grid() {
+        assert_eq!(
+            WaveFunction::count_solutions(GameBoard::<2>::create_empty(), usize::MAX),
+            288
+        );
+    }
+
+    #[test]
+    fn stops_counting_at_limit() {
+        let gameboard = test_fixtures::classic();
+        assert_eq!(WaveFunction::count_solutions(gameboard, 2), 1);
+        assert_eq!(
+            WaveFunction::count_solutions(GameBoard::<3>::create_empty(), 5),
+            5
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use super::{wave_function_collapse::Propagation, GameBoard};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+pub mod strategies;
+
+use strategies::{
+    AnnealingStrategy, BacktrackingStrategy, DancingLinksStrategy, ExploratoryStrategy,
+    GeneticStrategy, LogicalStrategy, SatStrategy, WaveFunctionStrategy,
+};
+
+/// Shared flag another thread can raise to stop a running solver.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GaveUpReason {
+    Cancelled,
+    Deadline,
+    NodeBudget,
+    /// The solver ran out of its own iterations or deductions.
+    Stalled,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SolverLimits {
+    pub deadline: Option<Instant>,
+    pub max_nodes: Option<u64>,
+    pub cancellation: CancellationToken,
+}
+
+impl SolverLimits {
+    pub fn timeout(duration: Duration) -> Self {
+        Self {
+            deadline: Some(Instant::now() + duration),
+            ..Self::default()
+        }
+    }
+
+    /// Reason to stop a search that already visited `nodes` nodes, if any.
+    pub fn check(&self, nodes: u64) -> Option<GaveUpReason> {
+        match self.max_nodes {
+            _ if self.cancellation.is_cancelled() => Some(GaveUpReason::Cancelled),
+            Some(max_nodes) if nodes >= max_nodes => Some(GaveUpReason::NodeBudget),
+            _ if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline) =>
+            {
+                Some(GaveUpReason::Deadline)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Work done by a solver; counters a solver has no notion of stay at zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolverStats {
+    /// Search nodes, generations or iterations, depending on the solver.
+    pub nodes: u64,
+    /// Guesses undone, or conflicts for the SAT solver.
+    pub backtracks: u64,
+    pub max_depth: usize,
+    /// Placements forced by propagation rather than guessed.
+    pub propagations: u64,
+    pub eliminations: u64,
+    /// High-water mark of the choice points the wave function kept for backtracking.
+    pub max_saved_frames: usize,
+    /// Times the wave function abandoned its search tree and started over.
+    pub restarts: u64,
+    pub elapsed: Duration,
+}
+
+impl SolverStats {
+    pub const CSV_HEADER: &'static str =
+        "nodes,backtracks,max_depth,propagations,eliminations,max_saved_frames,restarts,elapsed_us";
+
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            self.nodes,
+            self.backtracks,
+            self.max_depth,
+            self.propagations,
+            self.eliminations,
+            self.max_saved_frames,
+            self.restarts,
+            self.elapsed.as_micros()
+        )
+    }
+}
+
+impl std::ops::AddAssign for SolverStats {
+    fn add_assign(&mut self, other: Self) {
+        self.nodes += other.nodes;
+        self.backtracks += other.backtracks;
+        self.max_depth = self.max_depth.max(other.max_depth);
+        self.propagations += other.propagations;
+        self.eliminations += other.eliminations;
+        self.max_saved_frames = self.max_saved_frames.max(other.max_saved_frames);
+        self.restarts += other.restarts;
+        self.elapsed += other.elapsed;
+    }
+}
+
+impl fmt::Display for SolverStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} nodes, {} backtracks, depth {}, {} propagations, {} eliminations, {} saved frames, {} restarts in {:?}",
+            self.nodes,
+            self.backtracks,
+            self.max_depth,
+            self.propagations,
+            self.eliminations,
+            self.max_saved_frames,
+            self.restarts,
+            self.elapsed
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum SolveOutcome<const BOX_SIZE: usize> {
+    Solved(GameBoard<BOX_SIZE>),
+    Unsolvable,
+    GaveUp {
+        reason: GaveUpReason,
+        stats: SolverStats,
+        /// Board with the most cells filled that the search reached.
+        deepest: GameBoard<BOX_SIZE>,
+    },
+}
+
+pub trait Solver<const BOX_SIZE: usize> {
+    fn name(&self) -> &'static str;
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE>;
+
+    /// Counts solutions up to `limit`, or `None` when the solver cannot tell, because it does not
+    /// search exhaustively or gave up.
+    fn count_solutions(&mut self, gameboard: &GameBoard<BOX_SIZE>, limit: usize) -> Option<usize>;
+
+    /// Statistics of the latest `solve` or `count_solutions` call.
+    fn stats(&self) -> SolverStats;
+
+    fn solve_with_stats(
+        &mut self,
+        gameboard: &GameBoard<BOX_SIZE>,
+    ) -> (SolveOutcome<BOX_SIZE>, SolverStats) {
+        let outcome = self.solve(gameboard);
+        (outcome, self.stats())
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits);
+}
+
+/// Every solver with its default settings, in the order binaries list them.
+pub fn registry<const BOX_SIZE: usize>() -> Vec<Box<dyn Solver<BOX_SIZE>>> {
+    vec![
+        Box::<BacktrackingStrategy>::default(),
+        Box::<DancingLinksStrategy>::default(),
+        Box::<WaveFunctionStrategy>::default(),
+        Box::<ExploratoryStrategy>::default(),
+        Box::<AnnealingStrategy>::default(),
+        Box::<GeneticStrategy>::default(),
+        Box::<SatStrategy>::default(),
+        Box::<LogicalStrategy>::default(),
+    ]
+}
+
+pub fn solver_names<const BOX_SIZE: usize>() -> Vec<&'static str> {
+    registry::<BOX_SIZE>()
+        .iter()
+        .map(|solver| solver.name())
+        .collect()
+}
+
+pub fn solver_by_name<const BOX_SIZE: usize>(name: &str) -> Option<Box<dyn Solver<BOX_SIZE>>> {
+    registry().into_iter().find(|solver| solver.name() == name)
+}
+
+/// Counts the work of a single search, tells it when to stop and keeps the deepest board it
+/// reached.
+pub(in crate::core) struct SearchMonitor<const BOX_SIZE: usize> {
+    limits: SolverLimits,
+    stats: SolverStats,
+    start_time: Instant,
+    gave_up: Option<GaveUpReason>,
+    deepest: Option<(usize, GameBoard<BOX_SIZE>)>,
+}
+
+impl<const BOX_SIZE: usize> Default for SearchMonitor<BOX_SIZE> {
+    fn default() -> Self {
+        Self::new(SolverLimits::default())
+    }
+}
+
+impl<const BOX_SIZE: usize> SearchMonitor<BOX_SIZE> {
+    pub(in crate::core) fn new(limits: SolverLimits) -> Self {
+        Self {
+            limits,
+            stats: SolverStats::default(),
+            start_time: Instant::now(),
+            gave_up: None,
+            deepest: None,
+        }
+    }
+
+    /// Records a search node and returns whether the search may go on.
+    pub(in crate::core) fn visit(&mut self) -> bool {
+        if self.gave_up.is_none() {
+            self.gave_up = self.limits.check(self.stats.nodes);
+        }
+        if self.gave_up.is_some() {
+            return false;
+        }
+        self.stats.nodes += 1;
+        true
+    }
+
+    pub(in crate::core) fn backtracked(&mut self) {
+        self.stats.backtracks += 1;
+    }
+
+    pub(in crate::core) fn descended(&mut self, depth: usize) {
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+    }
+
+    pub(in crate::core) fn propagated(&mut self, propagation: Propagation) {
+        self.stats.propagations += propagation.forced_placements as u64;
+        self.stats.eliminations += propagation.eliminations as u64;
+    }
+
+    /// Takes over the counters of a search that kept its own statistics, such as one spread over
+    /// threads.
+    pub(in crate::core) fn record(&mut self, stats: SolverStats) {
+        self.stats = SolverStats {
+            elapsed: self.stats.elapsed,
+            ..stats
+        };
+    }
+
+    pub(in crate::core) fn give_up(&mut self, reason: GaveUpReason) {
+        self.gave_up.get_or_insert(reason);
+    }
+
+    /// Keeps `gameboard` when `progress`, usually the number of filled cells or the search depth,
+    /// beats every board seen so far.
+    pub(in crate::core) fn reached(
+        &mut self,
+        progress: usize,
+        gameboard: impl FnOnce() -> GameBoard<BOX_SIZE>,
+    ) {
+        match self.deepest {
+            Some((deepest_progress, _)) if deepest_progress >= progress => {}
+            _ => self.deepest = Some((progress, gameboard())),
+        }
+    }
+
+    pub(in crate::core) fn is_interrupted(&self) -> bool {
+        self.gave_up.is_some()
+    }
+
+    pub(in crate::core) fn limits(&self) -> &SolverLimits {
+        &self.limits
+    }
+
+    pub(in crate::core) fn finish(&self) -> SolverStats {
+        SolverStats {
+            elapsed: self.start_time.elapsed(),
+            ..self.stats
+        }
+    }
+
+    /// Gives up with the deepest board seen, or `fallback` when the search recorded none.
+    pub(in crate::core) fn gave_up_outcome(
+        &mut self,
+        fallback: GameBoard<BOX_SIZE>,
+    ) -> SolveOutcome<BOX_SIZE> {
+        SolveOutcome::GaveUp {
+            reason: self.gave_up.unwrap_or(GaveUpReason::Stalled),
+            stats: self.finish(),
+            deepest: self
+                .deepest
+                .take()
+                .map_or(fallback, |(_, gameboard)| gameboard),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        solver_by_name, solver_names, CancellationToken, GaveUpReason, SolveOutcome, SolverLimits,
+        SolverStats,
+    };
+    use crate::core::{test_fixtures, GameBoard};
+    use std::time::Instant;
+
+    #[test]
+    fn every_registered_solver_solves_grid() {
+        let gameboard = test_fixtures::classic();
+
+        for name in solver_names::<3>() {
+            let mut solver = solver_by_name::<3>(name).unwrap();
+            match solver.solve(&gameboard) {
+                SolveOutcome::Solved(solution) => assert!(solution.is_solved(), "{}", name),
+                outcome => panic!("{} ended with {:?}", name, outcome),
+            }
+
+            match solver.count_solutions(&gameboard, 2) {
+                Some(count) => assert_eq!(count, 1, "{}", name),
+                None => assert!(matches!(name, "exploratory" | "annealing" | "genetic")),
+            }
+        }
+        assert!(solver_by_name::<3>("unknown").is_none());
+    }
+
+    #[test]
+    fn solvers_give_up_when_cancelled_or_past_deadline() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let cancelled = SolverLimits {
+            cancellation: token,
+            ..SolverLimits::default()
+        };
+        let expired = SolverLimits {
+            deadline: Some(Instant::now()),
+            ..SolverLimits::default()
+        };
+
+        for name in solver_names::<3>() {
+            for (limits, expected) in [
+                (&cancelled, GaveUpReason::Cancelled),
+                (&expired, GaveUpReason::Deadline),
+            ] {
+                let mut solver = solver_by_name::<3>(name).unwrap();
+                solver.set_limits(limits.clone());
+
+                match solver.solve(&GameBoard::<3>::create_empty()) {
+                    SolveOutcome::GaveUp { reason, .. } => assert_eq!(reason, expected, "{}", name),
+                    outcome => panic!("{} ended with {:?}", name, outcome),
+                }
+                assert_eq!(
+                    solver.count_solutions(&GameBoard::<3>::create_empty(), 2),
+                    None,
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn node_budget_keeps_deepest_board() {
+        let gameboard = test_fixtures::hard();
+        let filled = |gameboard: &GameBoard<3>| {
+            gameboard
+                .as_raw()
+                .iter()
+                .flatten()
+                .filter(|&&val| val != 0)
+                .count()
+        };
+
+        let mut solver = solver_by_name::<3>("backtracking").unwrap();
+        solver.set_limits(SolverLimits {
+            max_nodes: Some(20),
+            ..SolverLimits::default()
+        });
+
+        match solver.solve(&gameboard) {
+            SolveOutcome::GaveUp {
+                reason,
+                stats,
+                deepest,
+            } => {
+                assert_eq!(reason, GaveUpReason::NodeBudget);
+                assert_eq!(stats.nodes, 20);
+                assert!(deepest.is_consistent());
+                assert!(filled(&deepest) > filled(&gameboard));
+            }
+            outcome => panic!("ended with {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn stats_describe_search_effort() {
+        let gameboard = test_fixtures::hard();
+
+        let (solved, stats) = gameboard
+            .clone()
+            .backtracking_solver_with_stats(Default::default());
+        assert!(solved);
+        assert!(stats.backtracks > 0 && stats.max_depth > 0);
+        assert_eq!(stats.max_saved_frames, 0);
+
+        let mut solver = solver_by_name::<3>("wave-function").unwrap();
+        let (outcome, stats) = solver.solve_with_stats(&gameboard);
+        assert!(matches!(outcome, SolveOutcome::Solved(_)));
+        assert!(stats.propagations > 0 && stats.eliminations > 0);
+        assert_eq!(stats.max_saved_frames, stats.max_depth);
+        assert_eq!(
+            stats.to_csv().split(',').count(),
+            SolverStats::CSV_HEADER.split(',').count()
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use super::{SearchMonitor, SolveOutcome, Solver, SolverLimits, SolverStats};
+use crate::core::{
+    backtracking_solver::EmptyCellHeuristic,
+    dancing_links::DancingLinks,
+    genetic_search::GeneticParameters,
+    logical_solver::{LogicalSolver, SolverOptions, Technique},
+    sat::cdcl::{Cdcl, SatResult},
+    stochastic_search::{AnnealingSchedule, StochasticOutcome},
+    wave_function_collapse::{
+        CollapseOptions, Propagation, RestartPolicy, WaveFunction, WaveState,
+    },
+    GameBoard,
+};
+
+#[derive(Default)]
+pub struct BacktrackingStrategy {
+    pub heuristic: EmptyCellHeuristic,
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for BacktrackingStrategy {
+    fn name(&self) -> &'static str {
+        "backtracking"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let mut solution = gameboard.clone();
+        let solved = solution.backtracking_solver_monitored(self.heuristic, &mut monitor);
+        self.stats = monitor.finish();
+
+        match (solved, monitor.is_interrupted()) {
+            (true, _) => SolveOutcome::Solved(solution),
+            (false, true) => monitor.gave_up_outcome(solution),
+            (false, false) => SolveOutcome::Unsolvable,
+        }
+    }
+
+    fn count_solutions(&mut self, gameboard: &GameBoard<BOX_SIZE>, limit: usize) -> Option<usize> {
+        let mut solutions =
+            gameboard.solutions_monitored(self.heuristic, SearchMonitor::new(self.limits.clone()));
+        let count = solutions.by_ref().take(limit).count();
+        self.stats = solutions.monitor.finish();
+
+        match solutions.monitor.is_interrupted() {
+            true => None,
+            false => Some(count),
+        }
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
+
+#[derive(Default)]
+pub struct DancingLinksStrategy {
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl DancingLinksStrategy {
+    fn search<const BOX_SIZE: usize>(
+        &mut self,
+        gameboard: &GameBoard<BOX_SIZE>,
+        limit: usize,
+    ) -> (Vec<GameBoard<BOX_SIZE>>, SearchMonitor<BOX_SIZE>) {
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let solutions = DancingLinks::build(gameboard).solve_monitored(limit, &mut monitor);
+        self.stats = monitor.finish();
+        (solutions, monitor)
+    }
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for DancingLinksStrategy {
+    fn name(&self) -> &'static str {
+        "dancing-links"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        match self.search(gameboard, 1) {
+            (mut solutions, _) if !solutions.is_empty() => {
+                SolveOutcome::Solved(solutions.pop().unwrap())
+            }
+            (_, mut monitor) if monitor.is_interrupted() => {
+                monitor.gave_up_outcome(gameboard.clone())
+            }
+            _ => SolveOutcome::Unsolvable,
+        }
+    }
+
+    fn count_solutions(&mut self, gameboard: &GameBoard<BOX_SIZE>, limit: usize) -> Option<usize> {
+        match self.search(gameboard, limit) {
+            (_, monitor) if monitor.is_interrupted() => None,
+            (solutions, _) => Some(solutions.len()),
+        }
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
+
+#[derive(Default)]
+pub struct WaveFunctionStrategy {
+    pub options: CollapseOptions,
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for WaveFunctionStrategy {
+    fn name(&self) -> &'static str {
+        "wave-function"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let mut wave_fn = WaveFunction::with_options(gameboard.clone(), self.options.clone());
+        let solved = gameboard.is_consistent() && wave_fn.run(&mut monitor);
+        monitor.record(wave_fn.stats());
+        self.stats = monitor.finish();
+
+        match (solved, monitor.is_interrupted()) {
+            (true, _) => SolveOutcome::Solved(wave_fn.state.gameboard),
+            (false, true) => monitor.gave_up_outcome(wave_fn.state.gameboard),
+            (false, false) => SolveOutcome::Unsolvable,
+        }
+    }
+
+    fn count_solutions(&mut self, gameboard: &GameBoard<BOX_SIZE>, limit: usize) -> Option<usize> {
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        // A restart would revisit solutions that were already counted.
+        let options = CollapseOptions {
+            restarts: RestartPolicy::Never,
+            ..self.options.clone()
+        };
+        let mut wave_fn = WaveFunction::with_options(gameboard.clone(), options);
+        let mut count = 0;
+
+        if gameboard.is_consistent() {
+            while count < limit && wave_fn.run(&mut monitor) {
+                count += 1;
+                if !wave_fn.force_backtrack_prev_frame() {
+                    break;
+                }
+            }
+        }
+        monitor.record(wave_fn.stats());
+        self.stats = monitor.finish();
+
+        match monitor.is_interrupted() {
+            true => None,
+            false => Some(count),
+        }
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
+
+pub struct ExploratoryStrategy {
+    pub num_threads: usize,
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl Default for ExploratoryStrategy {
+    fn default() -> Self {
+        Self {
+            num_threads: std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            limits: SolverLimits::default(),
+            stats: SolverStats::default(),
+        }
+    }
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for ExploratoryStrategy {
+    fn name(&self) -> &'static str {
+        "exploratory"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let outcome = WaveState::build(gameboard.clone())
+            .exploratory_decomposition_monitored(self.num_threads, &mut monitor);
+        self.stats = monitor.finish();
+
+        match (outcome.solution, monitor.is_interrupted()) {
+            (Some(solution), _) => SolveOutcome::Solved(solution),
+            (None, true) => monitor.gave_up_outcome(gameboard.clone()),
+            (None, false) => SolveOutcome::Unsolvable,
+        }
+    }
+
+    /// Workers stop at the first solution, so there is nothing to count.
+    fn count_solutions(&mut self, _: &GameBoard<BOX_SIZE>, _: usize) -> Option<usize> {
+        None
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
+
+#[derive(Default)]
+pub struct AnnealingStrategy {
+    pub schedule: AnnealingSchedule,
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for AnnealingStrategy {
+    fn name(&self) -> &'static str {
+        "annealing"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        if !gameboard.is_consistent() {
+            return SolveOutcome::Unsolvable;
+        }
+
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let outcome = gameboard.stochastic_search_monitored(&self.schedule, &mut monitor);
+        self.stats = monitor.finish();
+
+        stochastic_outcome(outcome, monitor)
+    }
+
+    fn count_solutions(&mut self, _: &GameBoard<BOX_SIZE>, _: usize) -> Option<usize> {
+        None
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
+
+#[derive(Default)]
+pub struct GeneticStrategy {
+    pub parameters: GeneticParameters,
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for GeneticStrategy {
+    fn name(&self) -> &'static str {
+        "genetic"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        if !gameboard.is_consistent() {
+            return SolveOutcome::Unsolvable;
+        }
+
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let outcome = gameboard.genetic_search_monitored(&self.parameters, &mut monitor);
+        self.stats = monitor.finish();
+
+        stochastic_outcome(outcome, monitor)
+    }
+
+    fn count_solutions(&mut self, _: &GameBoard<BOX_SIZE>, _: usize) -> Option<usize> {
+        None
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
+
+// Local search never proves a grid unsolvable, it only stops with its least conflicting board.
+fn stochastic_outcome<const BOX_SIZE: usize>(
+    outcome: StochasticOutcome<BOX_SIZE>,
+    mut monitor: SearchMonitor<BOX_SIZE>,
+) -> SolveOutcome<BOX_SIZE> {
+    match outcome {
+        StochasticOutcome::Solved(solution) => SolveOutcome::Solved(solution),
+        StochasticOutcome::BestEffort { gameboard, .. } => monitor.gave_up_outcome(gameboard),
+    }
+}
+
+#[derive(Default)]
+pub struct SatStrategy {
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for SatStrategy {
+    fn name(&self) -> &'static str {
+        "sat"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let mut cdcl = Cdcl::build(&gameboard.to_cnf());
+        let result = cdcl.solve_until(|| monitor.visit());
+        monitor.record(cdcl_stats(&cdcl));
+        self.stats = monitor.finish();
+
+        match result {
+            Some(SatResult::Satisfiable(model)) => {
+                SolveOutcome::Solved(gameboard.with_model(&model))
+            }
+            Some(SatResult::Unsatisfiable) => SolveOutcome::Unsolvable,
+            None => monitor.gave_up_outcome(gameboard.clone()),
+        }
+    }
+
+    // Each model found is ruled out with a blocking clause before solving again.
+    fn count_solutions(&mut self, gameboard: &GameBoard<BOX_SIZE>, limit: usize) -> Option<usize> {
+        let mut monitor = SearchMonitor::<BOX_SIZE>::new(self.limits.clone());
+        let mut cnf = gameboard.to_cnf();
+        let mut count = 0;
+        let mut stats = SolverStats::default();
+
+        while count < limit {
+            let mut cdcl = Cdcl::build(&cnf);
+            let result = cdcl.solve_until(|| monitor.visit());
+            stats += cdcl_stats(&cdcl);
+            monitor.record(stats);
+
+            match result {
+                Some(SatResult::Satisfiable(model)) => {
+                    count += 1;
+                    cnf.add_clause(
+                        model
+                            .into_iter()
+                            .filter(|&literal| literal > 0)
+                            .map(|literal| -literal)
+                            .collect(),
+                    );
+                }
+                Some(SatResult::Unsatisfiable) | None => break,
+            }
+        }
+        self.stats = monitor.finish();
+
+        match monitor.is_interrupted() {
+            true => None,
+            false => Some(count),
+        }
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
+
+fn cdcl_stats(cdcl: &Cdcl) -> SolverStats {
+    SolverStats {
+        nodes: cdcl.decisions(),
+        backtracks: cdcl.conflicts(),
+        max_depth: cdcl.max_decision_level(),
+        propagations: cdcl.propagations(),
+        ..SolverStats::default()
+    }
+}
+
+#[derive(Default)]
+pub struct LogicalStrategy {
+    pub options: SolverOptions,
+    limits: SolverLimits,
+    stats: SolverStats,
+}
+
+impl LogicalStrategy {
+    fn deduce<const BOX_SIZE: usize>(
+        &mut self,
+        gameboard: &GameBoard<BOX_SIZE>,
+    ) -> (LogicalSolver<BOX_SIZE>, SearchMonitor<BOX_SIZE>) {
+        let mut monitor = SearchMonitor::new(self.limits.clone());
+        let mut solver =
+            LogicalSolver::with_options(gameboard.clone(), &Technique::ALL, self.options.clone());
+        while monitor.visit() {
+            match solver.step() {
+                Some(step) => monitor.propagated(Propagation {
+                    forced_placements: step.placements.len(),
+                    eliminations: step.eliminations.len(),
+                }),
+                None => break,
+            }
+        }
+        self.stats = monitor.finish();
+
+        (solver, monitor)
+    }
+}
+
+impl<const BOX_SIZE: usize> Solver<BOX_SIZE> for LogicalStrategy {
+    fn name(&self) -> &'static str {
+        "logical"
+    }
+
+    fn solve(&mut self, gameboard: &GameBoard<BOX_SIZE>) -> SolveOutcome<BOX_SIZE> {
+        if !gameboard.is_consistent() {
+            return SolveOutcome::Unsolvable;
+        }
+
+        match self.deduce(gameboard) {
+            (solver, _) if solver.grid.is_solved() => SolveOutcome::Solved(solver.grid.gameboard),
+            (solver, mut monitor) => monitor.gave_up_outcome(solver.grid.gameboard),
+        }
+    }
+
+    /// A grid solved by deductions alone has exactly one solution, unless uniqueness was assumed.
+    fn count_solutions(&mut self, gameboard: &GameBoard<BOX_SIZE>, limit: usize) -> Option<usize> {
+        if !gameboard.is_consistent() {
+            return Some(0);
+        }
+
+        match self.deduce(gameboard) {
+            (solver, monitor)
+                if !monitor.is_interrupted()
+                    && solver.grid.is_solved()
+                    && !self.options.assume_uniqueness =>
+            {
+                Some(limit.min(1))
+            }
+            _ => None,
+        }
+    }
+
+    fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    fn set_limits(&mut self, limits: SolverLimits) {
+        self.limits = limits;
+    }
+}
@@ -0,0 +1,191 @@
+use super::{solver::SearchMonitor, Cell, GameBoard};
+use crate::utils::Vec2D;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+#[derive(Clone, Debug)]
+pub struct AnnealingSchedule {
+    pub initial_temperature: f64,
+    pub cooling_rate: f64,
+    pub min_temperature: f64,
+    /// Iterations without improving the best board before the temperature is reset.
+    pub reheat_after: usize,
+    pub max_reheats: usize,
+    pub max_iterations: usize,
+    pub seed: u64,
+}
+
+impl Default for AnnealingSchedule {
+    fn default() -> Self {
+        Self {
+            initial_temperature: 0.5,
+            cooling_rate: 0.99999,
+            min_temperature: 0.01,
+            reheat_after: 20_000,
+            max_reheats: 20,
+            max_iterations: 2_000_000,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum StochasticOutcome<const BOX_SIZE: usize> {
+    Solved(GameBoard<BOX_SIZE>),
+    BestEffort {
+        gameboard: GameBoard<BOX_SIZE>,
+        conflicts: usize,
+    },
+}
+
+impl<const BOX_SIZE: usize> GameBoard<BOX_SIZE> {
+    pub fn stochastic_search(&self, schedule: &AnnealingSchedule) -> StochasticOutcome<BOX_SIZE> {
+        self.stochastic_search_monitored(schedule, &mut SearchMonitor::default())
+    }
+
+    pub(super) fn stochastic_search_monitored(
+        &self,
+        schedule: &AnnealingSchedule,
+        monitor: &mut SearchMonitor<BOX_SIZE>,
+    ) -> StochasticOutcome<BOX_SIZE> {
+        let mut rng = StdRng::seed_from_u64(schedule.seed);
+
+        let mut gameboard = self.clone();
+        let free_cells = gameboard.fill_boxes(&mut rng);
+        let swappable_boxes = free_cells
+            .iter()
+            .filter(|cells| cells.len() > 1)
+            .collect::<Vec<_>>();
+
+        let mut conflicts = gameboard.conflicts();
+        let mut best_gameboard = gameboard.clone();
+        let mut best_conflicts = conflicts;
+
+        let mut temperature = schedule.initial_temperature;
+        let mut stale_iterations = 0;
+        let mut reheats = 0;
+
+        for _ in 0..schedule.max_iterations {
+            if best_conflicts == 0 || !monitor.visit() {
+                break;
+            }
+            let cells = match swappable_boxes.choose(&mut rng) {
+                Some(cells) => cells,
+                None => break,
+            };
+
+            let mut picked = cells.choose_multiple(&mut rng, 2);
+            let (first, second) = (*picked.next().unwrap(), *picked.next().unwrap());
+
+            let before = gameboard.swap_conflicts(first, second);
+            gameboard.swap_cells(first, second);
+            let after = gameboard.swap_conflicts(first, second);
+
+            let delta = after as f64 - before as f64;
+            if delta <= 0.0 || rng.gen::<f64>() < (-delta / temperature).exp() {
+                conflicts = conflicts + after - before;
+            } else {
+                gameboard.swap_cells(first, second);
+            }
+
+            if conflicts < best_conflicts {
+                best_conflicts = conflicts;
+                best_gameboard = gameboard.clone();
+                stale_iterations = 0;
+            } else {
+                stale_iterations += 1;
+            }
+
+            temperature = (temperature * schedule.cooling_rate).max(schedule.min_temperature);
+
+            if stale_iterations >= schedule.reheat_after && reheats < schedule.max_reheats {
+                temperature = schedule.initial_temperature;
+                stale_iterations = 0;
+                reheats += 1;
+            }
+        }
+
+        match best_conflicts {
+            0 => StochasticOutcome::Solved(best_gameboard),
+            conflicts => StochasticOutcome::BestEffort {
+                gameboard: best_gameboard,
+                conflicts,
+            },
+        }
+    }
+
+    pub(super) fn fill_boxes(&mut self, rng: &mut StdRng) -> Vec<Vec<Vec2D>> {
+        let mut free_cells = Vec::with_capacity(Self::BOARD_SIZE);
+
+        for box_idx in 0..Self::BOARD_SIZE {
+            let box_cells = self
+                .box_cell_positions(Self::box_start(box_idx))
+                .collect::<Vec<_>>();
+
+            let mut missing_nums = (1..=Self::BOARD_SIZE)
+                .filter(|&num| !box_cells.iter().any(|&pos| self[pos] == Cell::Given(num)))
+                .collect::<Vec<_>>();
+            missing_nums.shuffle(rng);
+
+            let box_free_cells = box_cells
+                .into_iter()
+                .filter(|&pos| !matches!(self[pos], Cell::Given(_)))
+                .collect::<Vec<_>>();
+
+            for (&pos, num) in box_free_cells.iter().zip(missing_nums) {
+                self[pos] = Cell::Guess(num);
+            }
+            free_cells.push(box_free_cells);
+        }
+
+        free_cells
+    }
+
+    pub(super) fn box_start(box_idx: usize) -> Vec2D {
+        Vec2D::new(box_idx / BOX_SIZE, box_idx % BOX_SIZE) * BOX_SIZE
+    }
+
+    pub(super) fn swap_cells(&mut self, first: Vec2D, second: Vec2D) {
+        let first_cell = self[first];
+        self[first] = self[second];
+        self[second] = first_cell;
+    }
+
+    fn unit_conflicts(&self, cells: impl Iterator<Item = Vec2D>) -> usize {
+        let mut seen = vec![false; Self::BOARD_SIZE + 1];
+        cells
+            .filter(|&pos| {
+                let num = self[pos].value();
+                num != 0 && std::mem::replace(&mut seen[num], true)
+            })
+            .count()
+    }
+
+    pub(super) fn row_conflicts(&self, row_idx: usize) -> usize {
+        self.unit_conflicts((0..Self::BOARD_SIZE).map(|col_idx| Vec2D::new(row_idx, col_idx)))
+    }
+
+    pub(super) fn col_conflicts(&self, col_idx: usize) -> usize {
+        self.unit_conflicts((0..Self::BOARD_SIZE).map(|row_idx| Vec2D::new(row_idx, col_idx)))
+    }
+
+    fn box_conflicts(&self, box_idx: usize) -> usize {
+        self.unit_conflicts(self.box_cell_positions(Self::box_start(box_idx)))
+    }
+
+    fn swap_conflicts(&self, first: Vec2D, second: Vec2D) -> usize {
+        let mut conflicts = self.row_conflicts(first.x()) + self.col_conflicts(first.y());
+        if second.x() != first.x() {
+            conflicts += self.row_conflicts(second.x());
+        }
+        if second.y() != first.y() {
+            conflicts += self.col_conflicts(second.y());
+        }
+        conflicts
+    }
+
+    pub fn conflicts(&self) -> usize {
+        (0..Self::BOARD_SIZE)
+            .map(|idx| self.row_conflicts(idx) + self.col_conflicts(idx) + self.box_conflicts(idx))
+            .sum()
+    }
+}
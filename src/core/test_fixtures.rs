@@ -0,0 +1,46 @@
+//! Boards shared by the solver tests.
+use super::GameBoard;
+
+/// Solvable with singles alone; its only solution is [`classic_solution`].
+pub(super) fn classic() -> GameBoard<3> {
+    GameBoard::new(vec![
+        vec![3, 0, 6, 5, 0, 8, 4, 0, 0],
+        vec![5, 2, 0, 0, 0, 0, 0, 0, 0],
+        vec![0, 8, 7, 0, 0, 0, 0, 3, 1],
+        vec![0, 0, 3, 0, 1, 0, 0, 8, 0],
+        vec![9, 0, 0, 8, 6, 3, 0, 0, 5],
+        vec![0, 5, 0, 0, 9, 0, 6, 0, 0],
+        vec![1, 3, 0, 0, 0, 0, 2, 5, 0],
+        vec![0, 0, 0, 0, 0, 0, 0, 7, 4],
+        vec![0, 0, 5, 2, 0, 6, 3, 0, 0],
+    ])
+}
+
+pub(super) fn classic_solution() -> Vec<Vec<usize>> {
+    vec![
+        vec![3, 1, 6, 5, 7, 8, 4, 9, 2],
+        vec![5, 2, 9, 1, 3, 4, 7, 6, 8],
+        vec![4, 8, 7, 6, 2, 9, 5, 3, 1],
+        vec![2, 6, 3, 4, 1, 5, 9, 8, 7],
+        vec![9, 7, 4, 8, 6, 3, 1, 2, 5],
+        vec![8, 5, 1, 7, 9, 2, 6, 4, 3],
+        vec![1, 3, 8, 9, 4, 7, 2, 5, 6],
+        vec![6, 9, 2, 3, 5, 1, 8, 7, 4],
+        vec![7, 4, 5, 2, 8, 6, 3, 1, 9],
+    ]
+}
+
+/// Logic alone stalls on it, so every complete solver has to guess and backtrack.
+pub(super) fn hard() -> GameBoard<3> {
+    GameBoard::new(vec![
+        vec![8, 0, 0, 0, 0, 0, 0, 0, 0],
+        vec![0, 0, 3, 6, 0, 0, 0, 0, 0],
+        vec![0, 7, 0, 0, 9, 0, 2, 0, 0],
+        vec![0, 5, 0, 0, 0, 7, 0, 0, 0],
+        vec![0, 0, 0, 0, 4, 5, 7, 0, 0],
+        vec![0, 0, 0, 1, 0, 0, 0, 3, 0],
+        vec![0, 0, 1, 0, 0, 0, 0, 6, 8],
+        vec![0, 0, 8, 5, 0, 0, 0, 1, 0],
+        vec![0, 9, 0, 0, 0, 0, 4, 0, 0],
+    ])
+}
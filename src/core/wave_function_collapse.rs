@@ -1,55 +1,186 @@
-use super::{Cell, GameBoard};
+use super::{
+    logical_solver::CandidateGrid,
+    solver::{SearchMonitor, SolverStats},
+    Cell, GameBoard,
+};
 use crate::utils::Vec2D;
 use keyed_priority_queue::{Entry, KeyedPriorityQueue};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::time::Instant;
 
-#[derive(Clone)]
-pub struct CellTile(Vec<usize>);
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CellTile(u64);
+
+impl CellTile {
+    pub const MAX_TILES: usize = u64::BITS as usize;
+
+    pub fn full(num_tiles: usize) -> Self {
+        match num_tiles {
+            Self::MAX_TILES => Self(u64::MAX),
+            _ => Self((1 << num_tiles) - 1),
+        }
+    }
+
+    pub fn single(tile: usize) -> Self {
+        debug_assert!(
+            (1..=Self::MAX_TILES).contains(&tile),
+            "tiles are numbered from 1"
+        );
+        Self(1 << (tile - 1))
+    }
 
-impl std::ops::Deref for CellTile {
-    type Target = Vec<usize>;
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, tile: usize) -> bool {
+        self.0 & Self::single(tile).0 != 0
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        match self.0 {
+            0 => None,
+            bits => Some(bits.trailing_zeros() as usize + 1),
+        }
+    }
+
+    pub fn insert(&mut self, tile: usize) {
+        self.0 |= Self::single(tile).0;
+    }
+
+    pub fn remove(&mut self, tile: usize) -> bool {
+        let was_present = self.contains(tile);
+        self.0 &= !Self::single(tile).0;
+        was_present
+    }
+
+    pub fn intersection(&self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(&self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn difference(&self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || match bits {
+            0 => None,
+            _ => {
+                let tile = bits.trailing_zeros() as usize + 1;
+                bits &= bits - 1;
+                Some(tile)
+            }
+        })
     }
 }
 
-impl PartialEq for CellTile {
-    fn eq(&self, other: &Self) -> bool {
-        self.0.len() == other.0.len()
+impl FromIterator<usize> for CellTile {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut cell_tile = Self::default();
+        iter.into_iter().for_each(|tile| cell_tile.insert(tile));
+        cell_tile
     }
 }
 
-impl Eq for CellTile {}
+impl std::fmt::Debug for CellTile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
 
+// Tiles are prioritised by entropy: the fewer candidates, the higher the priority.
 impl PartialOrd for CellTile {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.0.len().cmp(&self.0.len()))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for CellTile {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        other
+            .len()
+            .cmp(&self.len())
+            .then_with(|| other.0.cmp(&self.0))
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Propagation {
+    /// Cells collapsed as a consequence of the placement, not counting the placement itself.
+    pub forced_placements: usize,
+    pub eliminations: usize,
+}
+
+enum DigitPlaces {
+    Placed,
+    Missing,
+    Single(Vec2D),
+    Multiple,
+}
+
+pub(super) enum Decomposition<const BOX_SIZE: usize> {
+    Solved,
+    DeadEnd,
+    Branched(Vec<WaveState<BOX_SIZE>>),
+}
+
+/// A single change to a [`WaveState`] that can be reverted while backtracking.
+#[derive(Clone, Copy)]
+enum TrailEntry {
+    /// `tile` was removed from the candidates of a cell still in the queue.
+    Eliminated(Vec2D, usize),
+    /// The cell left the queue, holding these candidates at the time.
+    Collapsed(Vec2D, CellTile),
+}
+
 #[derive(Clone)]
 pub struct WaveState<const BOX_SIZE: usize> {
     pub gameboard: GameBoard<BOX_SIZE>,
     pub entropy_queue: KeyedPriorityQueue<Vec2D, CellTile>,
+    contradiction: bool,
+    trail: Option<Vec<TrailEntry>>,
+}
+
+impl<const BOX_SIZE: usize> From<&CandidateGrid<BOX_SIZE>> for WaveState<BOX_SIZE> {
+    fn from(grid: &CandidateGrid<BOX_SIZE>) -> Self {
+        let mut entropy_queue = KeyedPriorityQueue::new();
+        for pos in grid.empty_cells() {
+            entropy_queue.push(pos, grid.candidates(pos));
+        }
+
+        Self {
+            gameboard: grid.gameboard.clone(),
+            entropy_queue,
+            contradiction: false,
+            trail: None,
+        }
+    }
 }
 
 impl<const BOX_SIZE: usize> WaveState<BOX_SIZE> {
     pub fn build(gameboard: GameBoard<BOX_SIZE>) -> Self {
+        assert!(gameboard.board_size() <= CellTile::MAX_TILES);
+
         let mut non_collapsed_cells = KeyedPriorityQueue::new();
 
         for i in 0..gameboard.board_size() {
             for j in 0..gameboard.board_size() {
                 if gameboard[Vec2D::new(i, j)] == Cell::Empty {
-                    non_collapsed_cells.push(
-                        Vec2D::new(i, j),
-                        CellTile((1..=gameboard.board_size()).collect()),
-                    );
+                    non_collapsed_cells
+                        .push(Vec2D::new(i, j), CellTile::full(gameboard.board_size()));
                 }
             }
         }
@@ -57,12 +188,17 @@ impl<const BOX_SIZE: usize> WaveState<BOX_SIZE> {
         let mut simulation = Self {
             gameboard,
             entropy_queue: non_collapsed_cells,
+            contradiction: false,
+            trail: None,
         };
 
         for i in 0..simulation.gameboard.board_size() {
             for j in 0..simulation.gameboard.board_size() {
-                if let Cell::Given(given_state) = simulation.gameboard[Vec2D::new(i, j)] {
-                    simulation.apply_heuristics(Vec2D::new(i, j), given_state);
+                match simulation.gameboard[Vec2D::new(i, j)] {
+                    Cell::Given(placed_state) | Cell::Guess(placed_state) => {
+                        simulation.apply_heuristics(Vec2D::new(i, j), placed_state);
+                    }
+                    Cell::Empty => {}
                 }
             }
         }
@@ -70,88 +206,498 @@ impl<const BOX_SIZE: usize> WaveState<BOX_SIZE> {
         simulation
     }
 
-    fn heuristics_on_cell(&mut self, pos: Vec2D, removing_tile: usize) {
+    fn heuristics_on_cell(&mut self, pos: Vec2D, removing_tile: usize) -> Option<CellTile> {
         match self.entropy_queue.entry(pos) {
             Entry::Occupied(entry) => {
-                let next_tiles = CellTile(
-                    entry
-                        .get_priority()
+                let mut next_tiles = *entry.get_priority();
+                if !next_tiles.remove(removing_tile) {
+                    return None;
+                }
+                entry.set_priority(next_tiles);
+                self.record(TrailEntry::Eliminated(pos, removing_tile));
+                Some(next_tiles)
+            }
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    fn record(&mut self, entry: TrailEntry) {
+        if let Some(trail) = &mut self.trail {
+            trail.push(entry);
+        }
+    }
+
+    fn trail_len(&self) -> usize {
+        self.trail.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Reverts every recorded change past `trail_len`, newest first.
+    fn undo_to(&mut self, trail_len: usize) {
+        let undone = match &mut self.trail {
+            Some(trail) => trail.split_off(trail_len),
+            None => return,
+        };
+
+        for entry in undone.into_iter().rev() {
+            match entry {
+                TrailEntry::Eliminated(pos, tile) => {
+                    if let Entry::Occupied(entry) = self.entropy_queue.entry(pos) {
+                        let mut tiles = *entry.get_priority();
+                        tiles.insert(tile);
+                        entry.set_priority(tiles);
+                    }
+                }
+                TrailEntry::Collapsed(pos, tiles) => {
+                    self.gameboard[pos] = Cell::Empty;
+                    self.entropy_queue.push(pos, tiles);
+                }
+            }
+        }
+        self.contradiction = false;
+    }
+
+    fn apply_heuristics(&mut self, pos: Vec2D, given_tile: usize) -> Vec<(Vec2D, CellTile)> {
+        let board_size = self.gameboard.board_size();
+        let peers = (0..board_size)
+            .flat_map(|i| [Vec2D::new(i, pos.y()), Vec2D::new(pos.x(), i)])
+            .chain(self.gameboard.box_cell_positions(pos))
+            .collect::<Vec<_>>();
+
+        peers
+            .into_iter()
+            .filter_map(|peer| Some((peer, self.heuristics_on_cell(peer, given_tile)?)))
+            .collect()
+    }
+
+    pub fn is_contradiction(&self) -> bool {
+        self.contradiction
+            || matches!(self.entropy_queue.peek(), Some((_, min_entropy_tiles)) if min_entropy_tiles.is_empty())
+    }
+
+    /// Collapses `pos` and propagates naked and hidden singles until nothing changes or a cell
+    /// or digit runs out of places.
+    pub(super) fn collapse_cell(&mut self, pos: Vec2D, chosen_tile: usize) -> Propagation {
+        let origin = pos;
+        let mut propagation = Propagation::default();
+        let mut pending = vec![(pos, chosen_tile)];
+
+        while let Some((pos, tile)) = pending.pop() {
+            if self.contradiction {
+                break;
+            }
+            if self.gameboard[pos] != Cell::Empty {
+                self.contradiction = self.gameboard[pos].value() != tile;
+                continue;
+            }
+            let prev_tiles = match self.entropy_queue.remove(&pos) {
+                Some(tiles) => {
+                    self.record(TrailEntry::Collapsed(pos, tiles));
+                    tiles
+                }
+                None => {
+                    self.contradiction = true;
+                    break;
+                }
+            };
+            if !prev_tiles.contains(tile) {
+                self.contradiction = true;
+                break;
+            }
+
+            self.gameboard[pos] = Cell::Guess(tile);
+            if pos != origin {
+                propagation.forced_placements += 1;
+            }
+
+            let eliminated = self.apply_heuristics(pos, tile);
+            propagation.eliminations += eliminated.len();
+
+            for &(peer, tiles) in eliminated.iter() {
+                match tiles.len() {
+                    0 => self.contradiction = true,
+                    1 => pending.push((peer, tiles.first().unwrap())),
+                    _ => {}
+                }
+            }
+
+            // Only the placed digit left the peers, while `pos` itself stopped offering the rest.
+            let mut unit_checks = eliminated
+                .iter()
+                .flat_map(|&(peer, _)| self.units_of(peer))
+                .map(|unit| (unit, tile))
+                .collect::<Vec<_>>();
+            for unit in self.units_of(pos) {
+                unit_checks.extend(
+                    prev_tiles
+                        .difference(CellTile::single(tile))
                         .iter()
-                        .map(|&tile| tile)
-                        .filter(|&tile| tile != removing_tile)
-                        .collect(),
+                        .map(|other| (unit.clone(), other)),
                 );
-                entry.set_priority(next_tiles);
             }
-            Entry::Vacant(_) => {}
+
+            for (unit, digit) in unit_checks {
+                match self.digit_places(&unit, digit) {
+                    DigitPlaces::Missing => self.contradiction = true,
+                    DigitPlaces::Single(single_pos) => pending.push((single_pos, digit)),
+                    DigitPlaces::Placed | DigitPlaces::Multiple => {}
+                }
+            }
+        }
+
+        propagation
+    }
+
+    fn units_of(&self, pos: Vec2D) -> [Vec<Vec2D>; 3] {
+        let board_size = self.gameboard.board_size();
+        [
+            (0..board_size).map(|j| Vec2D::new(pos.x(), j)).collect(),
+            (0..board_size).map(|i| Vec2D::new(i, pos.y())).collect(),
+            self.gameboard.box_cell_positions(pos).collect(),
+        ]
+    }
+
+    fn digit_places(&self, unit: &[Vec2D], digit: usize) -> DigitPlaces {
+        let mut places = unit.iter().filter(|pos| {
+            matches!(self.entropy_queue.get_priority(pos), Some(tiles) if tiles.contains(digit))
+        });
+
+        match (places.next(), places.next()) {
+            _ if unit.iter().any(|&pos| self.gameboard[pos].value() == digit) => {
+                DigitPlaces::Placed
+            }
+            (None, _) => DigitPlaces::Missing,
+            (Some(&pos), None) => DigitPlaces::Single(pos),
+            (Some(_), Some(_)) => DigitPlaces::Multiple,
+        }
+    }
+
+    /// Peers that would lose `digit` as a candidate if it were placed at `pos`.
+    fn constrained_peers(&self, pos: Vec2D, digit: usize) -> usize {
+        let board_size = self.gameboard.board_size();
+        (0..board_size)
+            .flat_map(|i| [Vec2D::new(i, pos.y()), Vec2D::new(pos.x(), i)])
+            .chain(
+                self.gameboard
+                    .box_cell_positions(pos)
+                    .filter(|peer| peer.x() != pos.x() && peer.y() != pos.y()),
+            )
+            .filter(|&peer| peer != pos)
+            .filter(|peer| {
+                matches!(self.entropy_queue.get_priority(peer), Some(tiles) if tiles.contains(digit))
+            })
+            .count()
+    }
+
+    pub(super) fn decompose(&mut self) -> Decomposition<BOX_SIZE> {
+        loop {
+            if self.is_contradiction() {
+                return Decomposition::DeadEnd;
+            }
+
+            match self.entropy_queue.peek() {
+                None => return Decomposition::Solved,
+                Some((&min_entropy_pos, min_entropy_tiles)) if min_entropy_tiles.len() == 1 => {
+                    let only_tile = min_entropy_tiles.first().unwrap();
+                    self.collapse_cell(min_entropy_pos, only_tile);
+                }
+                Some((&min_entropy_pos, min_entropy_tiles)) => {
+                    let branches = min_entropy_tiles
+                        .iter()
+                        .map(|tile| {
+                            let mut cloned_state = self.clone();
+                            cloned_state.collapse_cell(min_entropy_pos, tile);
+                            cloned_state
+                        })
+                        .collect();
+                    return Decomposition::Branched(branches);
+                }
+            }
         }
     }
 
-    fn apply_heuristics(&mut self, pos: Vec2D, given_tile: usize) {
-        for i in 0..self.gameboard.board_size() {
-            self.heuristics_on_cell(Vec2D::new(i, pos.y()), given_tile);
-            self.heuristics_on_cell(Vec2D::new(pos.x(), i), given_tile);
+    pub fn recursive_decomposition(mut self) -> Option<GameBoard<BOX_SIZE>> {
+        match self.decompose() {
+            Decomposition::Solved => Some(self.gameboard),
+            Decomposition::DeadEnd => None,
+            Decomposition::Branched(branches) => branches
+                .into_iter()
+                .find_map(|branch| branch.recursive_decomposition()),
         }
+    }
+}
+
+#[derive(Debug)]
+pub enum CollapseResult<const BOX_SIZE: usize> {
+    Solved(GameBoard<BOX_SIZE>),
+    Unsolvable,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValueOrdering {
+    #[default]
+    First,
+    Random,
+    /// Prefers the candidate that removes the fewest candidates from the peers.
+    LeastConstraining,
+}
 
-        self.gameboard
-            .box_cell_positions(pos)
-            .for_each(|pos| self.heuristics_on_cell(pos, given_tile));
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TieBreaking {
+    /// Whichever of the equal-entropy cells the queue yields first.
+    #[default]
+    Queue,
+    Random,
+}
+
+/// When to abandon the current search tree and start over from the initial board with a new
+/// seed. Budgets count backtracks since the last restart, and restarts only explore a different
+/// tree with random value ordering or tie-breaking.
+#[derive(Clone, Debug, Default)]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    /// Budgets of `unit` times the Luby sequence 1, 1, 2, 1, 1, 2, 4, ...
+    Luby { unit: u64 },
+    /// Budgets starting at `initial` and multiplied by `factor` after every restart. A factor
+    /// of one or less may restart forever on unsolvable boards.
+    Geometric { initial: u64, factor: f64 },
+}
+
+impl RestartPolicy {
+    /// Backtracks allowed once the search has restarted `restarts` times.
+    pub fn budget(&self, restarts: u64) -> Option<u64> {
+        match *self {
+            RestartPolicy::Never => None,
+            RestartPolicy::Luby { unit } => Some(unit.saturating_mul(luby(restarts + 1)).max(1)),
+            RestartPolicy::Geometric { initial, factor } => {
+                Some((((initial as f64) * factor.powf(restarts as f64)) as u64).max(1))
+            }
+        }
     }
 }
 
+/// The `i`-th term of the Luby sequence, counting from one.
+fn luby(mut i: u64) -> u64 {
+    loop {
+        let bits = u64::BITS - i.leading_zeros();
+        if i == (1 << bits) - 1 {
+            return 1 << (bits - 1);
+        }
+        i -= (1 << (bits - 1)) - 1;
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CollapseOptions {
+    pub value_ordering: ValueOrdering,
+    pub tie_breaking: TieBreaking,
+    pub seed: u64,
+    pub restarts: RestartPolicy,
+}
+
+/// A branching collapse, remembered so the other candidates of `pos` can be tried later.
+struct ChoicePoint {
+    pos: Vec2D,
+    tile: usize,
+    trail_len: usize,
+}
+
 pub struct WaveFunction<const BOX_SIZE: usize> {
-    prev_frames: Vec<WaveState<BOX_SIZE>>,
+    choice_points: Vec<ChoicePoint>,
     pub state: WaveState<BOX_SIZE>,
+    last_propagation: Propagation,
+    stats: SolverStats,
+    backtracks_at_restart: u64,
+    start_time: Instant,
+    options: CollapseOptions,
+    rng: StdRng,
 }
 
 impl<const BOX_SIZE: usize> WaveFunction<BOX_SIZE> {
     pub fn build(gameboard: GameBoard<BOX_SIZE>) -> Self {
+        Self::with_options(gameboard, CollapseOptions::default())
+    }
+
+    pub fn with_options(gameboard: GameBoard<BOX_SIZE>, options: CollapseOptions) -> Self {
+        Self::with_state(WaveState::build(gameboard), options)
+    }
+
+    /// Continues from a state that already carries eliminations, such as a stalled logical solve.
+    pub fn with_state(mut state: WaveState<BOX_SIZE>, options: CollapseOptions) -> Self {
+        state.trail = Some(Vec::new());
+
         Self {
-            prev_frames: Vec::new(),
-            state: WaveState::build(gameboard),
+            choice_points: Vec::new(),
+            state,
+            last_propagation: Propagation::default(),
+            stats: SolverStats::default(),
+            backtracks_at_restart: 0,
+            start_time: Instant::now(),
+            rng: StdRng::seed_from_u64(options.seed),
+            options,
         }
     }
 
-    // pub fn collapse_for(gameboard: GameBoard) -> Option<GameBoard> {
-    //     WaveState::build(gameboard)
-    //         .recur()
-    //         .map(|state| state.gameboard)
-    // }
+    pub fn collapse_for(gameboard: GameBoard<BOX_SIZE>) -> CollapseResult<BOX_SIZE> {
+        if !gameboard.is_consistent() {
+            return CollapseResult::Unsolvable;
+        }
+
+        match WaveState::build(gameboard).recursive_decomposition() {
+            Some(gameboard) => CollapseResult::Solved(gameboard),
+            None => CollapseResult::Unsolvable,
+        }
+    }
 
     pub fn simulate_generation(&mut self) -> bool {
-        match self.state.entropy_queue.peek() {
-            Some((_, min_entropy_tiles)) if min_entropy_tiles.is_empty() => return false,
-            Some((&min_entropy_pos, min_entropy_tiles)) => {
-                if min_entropy_tiles.len() > 1 {
-                    let mut cloned_state = self.state.clone();
-                    cloned_state.heuristics_on_cell(min_entropy_pos, min_entropy_tiles[0]);
-                    self.prev_frames.push(cloned_state);
-                }
+        if self.state.is_contradiction() {
+            return false;
+        }
 
-                let chosen_tile = min_entropy_tiles[0];
-                self.state.gameboard[min_entropy_pos] = Cell::Guess(chosen_tile);
+        if let Some(min_entropy_pos) = self.choose_cell() {
+            let min_entropy_tiles = *self
+                .state
+                .entropy_queue
+                .get_priority(&min_entropy_pos)
+                .unwrap();
+            let chosen_tile = self.choose_tile(min_entropy_pos, min_entropy_tiles);
 
-                self.state.entropy_queue.pop();
-                self.state.apply_heuristics(min_entropy_pos, chosen_tile);
+            if min_entropy_tiles.len() > 1 {
+                self.choice_points.push(ChoicePoint {
+                    pos: min_entropy_pos,
+                    tile: chosen_tile,
+                    trail_len: self.state.trail_len(),
+                });
+                self.stats.max_depth = self.stats.max_depth.max(self.depth());
+                self.stats.max_saved_frames =
+                    self.stats.max_saved_frames.max(self.choice_points.len());
             }
-            _ => {}
+
+            self.last_propagation = self.state.collapse_cell(min_entropy_pos, chosen_tile);
+            self.stats.nodes += 1;
+            self.stats.propagations += self.last_propagation.forced_placements as u64;
+            self.stats.eliminations += self.last_propagation.eliminations as u64;
         }
 
         true
     }
 
-    pub fn backtrack_prev_frame(&mut self) -> bool {
-        match self.state.entropy_queue.peek() {
-            Some((_, min_entropy_tiles)) if min_entropy_tiles.is_empty() => {
-                self.state = self.prev_frames.pop().unwrap();
-                true
+    fn choose_cell(&mut self) -> Option<Vec2D> {
+        let (&min_entropy_pos, min_entropy_tiles) = self.state.entropy_queue.peek()?;
+
+        match self.options.tie_breaking {
+            TieBreaking::Queue => Some(min_entropy_pos),
+            TieBreaking::Random => {
+                let min_entropy = min_entropy_tiles.len();
+                let mut tied_cells = self
+                    .state
+                    .entropy_queue
+                    .iter()
+                    .filter(|(_, tiles)| tiles.len() == min_entropy)
+                    .map(|(&pos, _)| pos)
+                    .collect::<Vec<_>>();
+                // Sorted so the seed alone decides the pick, whatever order the queue keeps.
+                tied_cells.sort_by_key(|pos| (pos.x(), pos.y()));
+                tied_cells.choose(&mut self.rng).copied()
             }
-            _ => false,
         }
     }
 
-    pub fn force_backtrack_prev_frame(&mut self) {
-        self.state = self.prev_frames.pop().unwrap();
+    fn choose_tile(&mut self, pos: Vec2D, tiles: CellTile) -> usize {
+        match self.options.value_ordering {
+            ValueOrdering::First => tiles.first().unwrap(),
+            ValueOrdering::Random => *tiles
+                .iter()
+                .collect::<Vec<_>>()
+                .choose(&mut self.rng)
+                .unwrap(),
+            ValueOrdering::LeastConstraining => tiles
+                .iter()
+                .min_by_key(|&tile| self.state.constrained_peers(pos, tile))
+                .unwrap(),
+        }
+    }
+
+    pub fn backtrack_prev_frame(&mut self) -> bool {
+        match self.state.is_contradiction() {
+            true => self.force_backtrack_prev_frame(),
+            false => false,
+        }
+    }
+
+    /// Eliminations and forced placements triggered by the latest collapse.
+    pub fn last_propagation(&self) -> Propagation {
+        self.last_propagation
+    }
+
+    pub fn depth(&self) -> usize {
+        self.choice_points.len()
+    }
+
+    /// Unwinds the trail to the latest choice point and rules out the tile tried there. The
+    /// elimination lands on the parent's part of the trail, so it is undone with the parent.
+    /// Returns `false`, changing nothing, when there is no choice point left to go back to.
+    pub fn force_backtrack_prev_frame(&mut self) -> bool {
+        let Some(choice) = self.choice_points.pop() else {
+            return false;
+        };
+        self.state.undo_to(choice.trail_len);
+        self.state.heuristics_on_cell(choice.pos, choice.tile);
+        self.stats.backtracks += 1;
+        true
+    }
+
+    fn restart_due(&self) -> bool {
+        self.options
+            .restarts
+            .budget(self.stats.restarts)
+            .is_some_and(|budget| self.stats.backtracks - self.backtracks_at_restart >= budget)
+    }
+
+    /// Abandons every choice point and starts over from the initial board with a new seed.
+    fn restart(&mut self) {
+        self.choice_points.clear();
+        self.state.undo_to(0);
+        self.stats.restarts += 1;
+        self.backtracks_at_restart = self.stats.backtracks;
+        self.rng = StdRng::seed_from_u64(self.options.seed.wrapping_add(self.stats.restarts));
+    }
+
+    /// Work done since the wave function was built.
+    pub fn stats(&self) -> SolverStats {
+        SolverStats {
+            elapsed: self.start_time.elapsed(),
+            ..self.stats
+        }
+    }
+
+    /// Steps until the board is solved, or returns `false` once every branch failed or the
+    /// monitor stopped the search. Failures past the restart budget start the search over.
+    pub(super) fn run(&mut self, monitor: &mut SearchMonitor<BOX_SIZE>) -> bool {
+        let board_area = self.state.gameboard.board_size() * self.state.gameboard.board_size();
+
+        loop {
+            if self.state.entropy_queue.is_empty() && !self.state.is_contradiction() {
+                return true;
+            }
+            if !monitor.visit() {
+                return false;
+            }
+            if !self.simulate_generation() {
+                if self.depth() == 0 {
+                    return false;
+                }
+                match self.restart_due() {
+                    true => self.restart(),
+                    false => {
+                        self.force_backtrack_prev_frame();
+                    }
+                }
+            } else if !self.state.is_contradiction() {
+                let filled = board_area - self.state.entropy_queue.len();
+                monitor.reached(filled, || self.state.gameboard.clone());
+            }
+        }
     }
 
     pub fn print(&self, stdout: &mut std::io::Stdout) -> Result<(), Box<dyn std::error::Error>> {
@@ -171,18 +717,15 @@ impl<const BOX_SIZE: usize> WaveFunction<BOX_SIZE> {
             )
             .as_bytes(),
         )?;
-        if let Some(prev_frame) = self.prev_frames.last() {
+        if let Some(choice) = self.choice_points.last() {
             stdout.write(b"\n\n")?;
-            stdout.write(format!("{:?}", prev_frame.gameboard).as_bytes())?;
-            stdout.write(format!("{}\n", prev_frame.entropy_queue.len()).as_bytes())?;
             stdout.write(
                 format!(
-                    "{:?}",
-                    prev_frame
-                        .entropy_queue
-                        .iter()
-                        .map(|entry| entry.0)
-                        .collect::<Vec<_>>()
+                    "depth {}, trying {} at {:?}, {} changes on the trail\n",
+                    self.depth(),
+                    choice.tile,
+                    choice.pos,
+                    self.state.trail_len()
                 )
                 .as_bytes(),
             )?;
@@ -192,3 +735,197 @@ impl<const BOX_SIZE: usize> WaveFunction<BOX_SIZE> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        CellTile, CollapseOptions, CollapseResult, RestartPolicy, TieBreaking, ValueOrdering,
+        WaveFunction, WaveState,
+    };
+    use crate::{
+        core::{solver::SearchMonitor, test_fixtures, GameBoard},
+        utils::Vec2D,
+    };
+
+    #[test]
+    fn cell_tile_set_operations() {
+        let full = CellTile::full(CellTile::MAX_TILES);
+        assert_eq!(full.len(), 64);
+        assert_eq!(full.first(), Some(1));
+        assert!(full.contains(64));
+        assert_eq!(CellTile::single(64).iter().collect::<Vec<_>>(), vec![64]);
+        assert_eq!(CellTile::full(9).len(), 9);
+        assert!(!CellTile::full(9).contains(10));
+
+        let empty = CellTile::default();
+        assert!(empty.is_empty());
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.iter().next(), None);
+
+        let mut tiles = [9, 2, 64, 5].into_iter().collect::<CellTile>();
+        assert_eq!(tiles.iter().collect::<Vec<_>>(), vec![2, 5, 9, 64]);
+        assert_eq!(tiles.len(), 4);
+        assert!(tiles.remove(64));
+        assert!(!tiles.remove(64));
+        assert_eq!(tiles.len(), 3);
+
+        let other = [5, 7, 9].into_iter().collect::<CellTile>();
+        assert_eq!(
+            tiles.intersection(other).iter().collect::<Vec<_>>(),
+            vec![5, 9]
+        );
+        assert_eq!(tiles.difference(other).iter().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(tiles.union(other).len(), 4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "tiles are numbered from 1")]
+    fn cell_tile_rejects_tile_zero() {
+        CellTile::single(0);
+    }
+
+    #[test]
+    fn collapses_to_solution() {
+        let gameboard = test_fixtures::classic();
+
+        match WaveFunction::collapse_for(gameboard) {
+            CollapseResult::Solved(solution) => {
+                assert_eq!(solution.as_raw(), test_fixtures::classic_solution())
+            }
+            CollapseResult::Unsolvable => panic!("expected a solution"),
+        }
+    }
+
+    #[test]
+    fn invalid_grid_is_unsolvable() {
+        let gameboard = GameBoard::<3>::new(vec![
+            vec![3, 0, 6, 5, 0, 5, 4, 0, 0],
+            vec![5, 2, 0, 0, 0, 0, 0, 0, 0],
+            vec![0, 8, 7, 0, 0, 0, 0, 3, 1],
+            vec![0, 0, 3, 0, 1, 0, 0, 8, 0],
+            vec![9, 0, 0, 8, 6, 3, 0, 0, 5],
+            vec![0, 5, 0, 0, 9, 0, 6, 0, 0],
+            vec![1, 3, 0, 0, 0, 0, 2, 5, 0],
+            vec![0, 0, 0, 0, 0, 0, 0, 7, 4],
+            vec![0, 0, 5, 2, 0, 6, 3, 0, 0],
+        ]);
+
+        assert!(matches!(
+            WaveFunction::collapse_for(gameboard),
+            CollapseResult::Unsolvable
+        ));
+    }
+
+    #[test]
+    fn propagation_forces_singles_and_detects_contradictions() {
+        let state = WaveState::build(test_fixtures::classic());
+
+        let mut right = state.clone();
+        let propagation = right.collapse_cell(Vec2D::new(0, 1), 1);
+        assert!(!right.is_contradiction());
+        assert_eq!(
+            right.entropy_queue.len() + propagation.forced_placements + 1,
+            state.entropy_queue.len()
+        );
+        assert!(propagation.forced_placements > 0 && propagation.eliminations > 0);
+
+        let mut wrong = state;
+        wrong.collapse_cell(Vec2D::new(0, 1), 9);
+        assert!(wrong.is_contradiction());
+    }
+
+    #[test]
+    fn seeded_orderings_are_reproducible_and_varied() {
+        let solve = |value_ordering, seed| {
+            let mut wave_fn = WaveFunction::with_options(
+                GameBoard::<3>::create_empty(),
+                CollapseOptions {
+                    value_ordering,
+                    tie_breaking: TieBreaking::Random,
+                    seed,
+                    ..Default::default()
+                },
+            );
+            assert!(wave_fn.run(&mut SearchMonitor::default()));
+            assert!(wave_fn.state.gameboard.is_solved());
+            wave_fn.state.gameboard.as_raw()
+        };
+
+        assert_eq!(
+            solve(ValueOrdering::Random, 7),
+            solve(ValueOrdering::Random, 7)
+        );
+        assert_ne!(
+            solve(ValueOrdering::Random, 7),
+            solve(ValueOrdering::Random, 8)
+        );
+        solve(ValueOrdering::LeastConstraining, 0);
+    }
+
+    #[test]
+    fn trail_backtracking_enumerates_every_grid() {
+        let mut wave_fn = WaveFunction::build(GameBoard::<2>::create_empty());
+        let mut solutions = std::collections::HashSet::new();
+
+        while wave_fn.run(&mut SearchMonitor::default()) {
+            assert!(wave_fn.state.gameboard.is_solved());
+            assert!(solutions.insert(wave_fn.state.gameboard.as_raw()));
+            if !wave_fn.force_backtrack_prev_frame() {
+                break;
+            }
+        }
+
+        assert_eq!(solutions.len(), 288);
+        assert_eq!(wave_fn.depth(), 0);
+        assert!(!wave_fn.force_backtrack_prev_frame());
+        assert_eq!(wave_fn.stats().max_saved_frames, wave_fn.stats().max_depth);
+    }
+
+    #[test]
+    fn restart_budgets_follow_their_schedules() {
+        let luby = RestartPolicy::Luby { unit: 2 };
+        assert_eq!(
+            (0..15).map(|i| luby.budget(i).unwrap()).collect::<Vec<_>>(),
+            vec![2, 2, 4, 2, 2, 4, 8, 2, 2, 4, 2, 2, 4, 8, 16]
+        );
+
+        let geometric = RestartPolicy::Geometric {
+            initial: 10,
+            factor: 1.5,
+        };
+        assert_eq!(
+            (0..4)
+                .map(|i| geometric.budget(i).unwrap())
+                .collect::<Vec<_>>(),
+            vec![10, 15, 22, 33]
+        );
+        assert_eq!(RestartPolicy::Never.budget(3), None);
+    }
+
+    #[test]
+    fn restarts_are_counted_and_still_solve() {
+        let gameboard = test_fixtures::hard();
+
+        for restarts in [
+            RestartPolicy::Luby { unit: 1 },
+            RestartPolicy::Geometric {
+                initial: 1,
+                factor: 1.5,
+            },
+        ] {
+            let mut wave_fn = WaveFunction::with_options(
+                gameboard.clone(),
+                CollapseOptions {
+                    value_ordering: ValueOrdering::Random,
+                    tie_breaking: TieBreaking::Random,
+                    seed: 3,
+                    restarts,
+                },
+            );
+            assert!(wave_fn.run(&mut SearchMonitor::default()));
+            assert!(wave_fn.state.gameboard.is_solved());
+            assert!(wave_fn.stats().restarts > 0);
+        }
+    }
+}
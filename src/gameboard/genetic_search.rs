@@ -0,0 +1,10 @@
+use super::GameBoard;
+use crate::core::{self, stochastic_search::StochasticOutcome};
+
+pub use crate::core::genetic_search::GeneticParameters;
+
+impl GameBoard {
+    pub fn genetic_search(&self, parameters: &GeneticParameters) -> StochasticOutcome<3> {
+        core::GameBoard::<3>::from(self.clone()).genetic_search(parameters)
+    }
+}
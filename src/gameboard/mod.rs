@@ -1,7 +1,8 @@
 pub mod backtracking_solver;
+pub mod genetic_search;
 pub mod stochastic_search;
 
-use crate::utils::Vec2D;
+use crate::{core, utils::Vec2D};
 
 const BOARD_SIZE: usize = 9;
 
@@ -42,7 +43,7 @@ impl Cell {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct GameBoard([[Cell; BOARD_SIZE]; BOARD_SIZE]);
 
 impl std::ops::Index<Vec2D> for GameBoard {
@@ -107,9 +108,56 @@ impl GameBoard {
     }
 }
 
+impl From<Cell> for core::Cell {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Empty => core::Cell::Empty,
+            Cell::Given(num) => core::Cell::Given(num as usize),
+            Cell::Guess(num) => core::Cell::Guess(num as usize),
+        }
+    }
+}
+
+impl From<core::Cell> for Cell {
+    fn from(cell: core::Cell) -> Self {
+        match cell {
+            core::Cell::Empty => Cell::Empty,
+            core::Cell::Given(num) => Cell::Given(num as u8),
+            core::Cell::Guess(num) => Cell::Guess(num as u8),
+        }
+    }
+}
+
+impl From<GameBoard> for core::GameBoard<3> {
+    fn from(gameboard: GameBoard) -> Self {
+        let mut unified = core::GameBoard::create_empty();
+        for i in 0..BOARD_SIZE {
+            for j in 0..BOARD_SIZE {
+                unified[Vec2D::new(i, j)] = gameboard[Vec2D::new(i, j)].into();
+            }
+        }
+        unified
+    }
+}
+
+impl From<core::GameBoard<3>> for GameBoard {
+    fn from(unified: core::GameBoard<3>) -> Self {
+        let mut gameboard = GameBoard::default();
+        for i in 0..BOARD_SIZE {
+            for j in 0..BOARD_SIZE {
+                gameboard.0[i][j] = unified[Vec2D::new(i, j)].into();
+            }
+        }
+        gameboard
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::GameBoard;
+    use super::{
+        stochastic_search::{AnnealingSchedule, StochasticOutcome},
+        GameBoard,
+    };
 
     #[test]
     fn has_correct_output() {
@@ -163,4 +211,68 @@ mod test {
 
         assert_eq!(gameboard.as_raw(), board);
     }
+
+    #[test]
+    fn stochastic_search_solves_grid() {
+        let gameboard = GameBoard::new([
+            [3, 0, 6, 5, 0, 8, 4, 0, 0],
+            [5, 2, 0, 0, 0, 0, 0, 0, 0],
+            [0, 8, 7, 0, 0, 0, 0, 3, 1],
+            [0, 0, 3, 0, 1, 0, 0, 8, 0],
+            [9, 0, 0, 8, 6, 3, 0, 0, 5],
+            [0, 5, 0, 0, 9, 0, 6, 0, 0],
+            [1, 3, 0, 0, 0, 0, 2, 5, 0],
+            [0, 0, 0, 0, 0, 0, 0, 7, 4],
+            [0, 0, 5, 2, 0, 6, 3, 0, 0],
+        ]);
+
+        match gameboard.stochastic_search(&AnnealingSchedule::default()) {
+            StochasticOutcome::Solved(solution) => assert_eq!(solution.conflicts(), 0),
+            StochasticOutcome::BestEffort { conflicts, .. } => {
+                panic!("stopped with {} conflicts", conflicts)
+            }
+        }
+    }
+
+    #[test]
+    fn counts_solutions_up_to_limit() {
+        let unique = GameBoard::new([
+            [3, 0, 6, 5, 0, 8, 4, 0, 0],
+            [5, 2, 0, 0, 0, 0, 0, 0, 0],
+            [0, 8, 7, 0, 0, 0, 0, 3, 1],
+            [0, 0, 3, 0, 1, 0, 0, 8, 0],
+            [9, 0, 0, 8, 6, 3, 0, 0, 5],
+            [0, 5, 0, 0, 9, 0, 6, 0, 0],
+            [1, 3, 0, 0, 0, 0, 2, 5, 0],
+            [0, 0, 0, 0, 0, 0, 0, 7, 4],
+            [0, 0, 5, 2, 0, 6, 3, 0, 0],
+        ]);
+        assert_eq!(unique.count_solutions(2), 1);
+
+        let under_constrained = GameBoard::new([
+            [3, 0, 6, 5, 0, 8, 4, 0, 0],
+            [5, 2, 0, 0, 0, 0, 0, 0, 0],
+            [0, 8, 7, 0, 0, 0, 0, 3, 1],
+            [0, 0, 3, 0, 1, 0, 0, 8, 0],
+            [9, 0, 0, 8, 6, 3, 0, 0, 5],
+            [0, 5, 0, 0, 9, 0, 6, 0, 0],
+            [1, 3, 0, 0, 0, 0, 2, 5, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(under_constrained.count_solutions(2), 2);
+
+        let invalid = GameBoard::new([
+            [3, 0, 6, 5, 0, 5, 4, 0, 0],
+            [5, 2, 0, 0, 0, 0, 0, 0, 0],
+            [0, 8, 7, 0, 0, 0, 0, 3, 1],
+            [0, 0, 3, 0, 1, 0, 0, 8, 0],
+            [9, 0, 0, 8, 6, 3, 0, 0, 5],
+            [0, 5, 0, 0, 9, 0, 6, 0, 0],
+            [1, 3, 0, 0, 0, 0, 2, 5, 0],
+            [0, 0, 0, 0, 0, 0, 0, 7, 4],
+            [0, 0, 5, 2, 0, 6, 3, 0, 0],
+        ]);
+        assert_eq!(invalid.count_solutions(2), 0);
+    }
 }
@@ -1 +1,10 @@
+use super::GameBoard;
+use crate::core;
 
+pub use crate::core::stochastic_search::{AnnealingSchedule, StochasticOutcome};
+
+impl GameBoard {
+    pub fn stochastic_search(&self, schedule: &AnnealingSchedule) -> StochasticOutcome<3> {
+        core::GameBoard::<3>::from(self.clone()).stochastic_search(schedule)
+    }
+}